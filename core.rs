@@ -1,7 +1,9 @@
 // Core traits and types for Rust Gymnasium (Step 3 of README)
 
-/// A minimal, serde-friendly info map (without pulling serde as a dependency).
-/// It stores small numbers of key-value pairs and is sufficient for early phases.
+use crate::spaces::Space;
+
+/// A minimal info map keyed by strings, preserving insertion order.
+/// It stores small numbers of key-value pairs; with the `serde` feature it serializes as a map.
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Info {
     entries: Vec<(String, InfoValue)>,
@@ -26,6 +28,30 @@ impl Info {
         self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
     }
 
+    /// Get a boolean value; `None` if missing or of another type.
+    pub fn get_bool(&self, key: &str) -> Option<bool> { self.get(key).and_then(InfoValue::as_bool) }
+
+    /// Get an integer value; `None` if missing or of another type.
+    pub fn get_i64(&self, key: &str) -> Option<i64> { self.get(key).and_then(InfoValue::as_i64) }
+
+    /// Get a float value; `None` if missing or of another type.
+    pub fn get_f64(&self, key: &str) -> Option<f64> { self.get(key).and_then(InfoValue::as_f64) }
+
+    /// Get a string value; `None` if missing or of another type.
+    pub fn get_str(&self, key: &str) -> Option<&str> { self.get(key).and_then(InfoValue::as_str) }
+
+    /// Get a float array; `None` if missing or of another type.
+    pub fn get_f64_array(&self, key: &str) -> Option<&[f64]> { self.get(key).and_then(InfoValue::as_f64_array) }
+
+    /// Get an integer array; `None` if missing or of another type.
+    pub fn get_i64_array(&self, key: &str) -> Option<&[i64]> { self.get(key).and_then(InfoValue::as_i64_array) }
+
+    /// Get a byte array; `None` if missing or of another type.
+    pub fn get_bytes(&self, key: &str) -> Option<&[u8]> { self.get(key).and_then(InfoValue::as_bytes) }
+
+    /// Get a nested map; `None` if missing or of another type.
+    pub fn get_map(&self, key: &str) -> Option<&Info> { self.get(key).and_then(InfoValue::as_map) }
+
     /// Iterate over entries.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &InfoValue)> {
         self.entries.iter().map(|(k, v)| (k.as_str(), v))
@@ -38,13 +64,68 @@ impl Info {
     pub fn len(&self) -> usize { self.entries.len() }
 }
 
-/// A small set of value types commonly used in info maps.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Info {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for (k, v) in &self.entries {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Info {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct InfoVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for InfoVisitor {
+            type Value = Info;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result { f.write_str("an info map") }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut access: A) -> std::result::Result<Info, A::Error> {
+                let mut info = Info::new();
+                while let Some((k, v)) = access.next_entry::<String, InfoValue>()? {
+                    info.insert(k, v);
+                }
+                Ok(info)
+            }
+        }
+
+        deserializer.deserialize_map(InfoVisitor)
+    }
+}
+
+/// Value types stored in info maps: scalars, numeric arrays, raw bytes and nested maps.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InfoValue {
     Bool(bool),
     I64(i64),
     F64(f64),
     Str(String),
+    /// Float vector, e.g. contact forces or per-objective values.
+    F64Array(Vec<f64>),
+    /// Integer vector, e.g. per-leg contact flags or counts.
+    I64Array(Vec<i64>),
+    /// Raw bytes, e.g. packed masks.
+    Bytes(Vec<u8>),
+    /// Nested info map.
+    Map(Info),
+}
+
+impl InfoValue {
+    pub fn as_bool(&self) -> Option<bool> { if let InfoValue::Bool(v) = self { Some(*v) } else { None } }
+    pub fn as_i64(&self) -> Option<i64> { if let InfoValue::I64(v) = self { Some(*v) } else { None } }
+    pub fn as_f64(&self) -> Option<f64> { if let InfoValue::F64(v) = self { Some(*v) } else { None } }
+    pub fn as_str(&self) -> Option<&str> { if let InfoValue::Str(v) = self { Some(v) } else { None } }
+    pub fn as_f64_array(&self) -> Option<&[f64]> { if let InfoValue::F64Array(v) = self { Some(v) } else { None } }
+    pub fn as_i64_array(&self) -> Option<&[i64]> { if let InfoValue::I64Array(v) = self { Some(v) } else { None } }
+    pub fn as_bytes(&self) -> Option<&[u8]> { if let InfoValue::Bytes(v) = self { Some(v) } else { None } }
+    pub fn as_map(&self) -> Option<&Info> { if let InfoValue::Map(v) = self { Some(v) } else { None } }
 }
 
 impl From<bool> for InfoValue { fn from(v: bool) -> Self { InfoValue::Bool(v) } }
@@ -54,6 +135,11 @@ impl From<f64> for InfoValue { fn from(v: f64) -> Self { InfoValue::F64(v) } }
 impl From<f32> for InfoValue { fn from(v: f32) -> Self { InfoValue::F64(v as f64) } }
 impl From<&str> for InfoValue { fn from(v: &str) -> Self { InfoValue::Str(v.to_string()) } }
 impl From<String> for InfoValue { fn from(v: String) -> Self { InfoValue::Str(v) } }
+impl From<Vec<f64>> for InfoValue { fn from(v: Vec<f64>) -> Self { InfoValue::F64Array(v) } }
+impl From<&[f32]> for InfoValue { fn from(v: &[f32]) -> Self { InfoValue::F64Array(v.iter().map(|&x| x as f64).collect()) } }
+impl From<Vec<i64>> for InfoValue { fn from(v: Vec<i64>) -> Self { InfoValue::I64Array(v) } }
+impl From<Vec<u8>> for InfoValue { fn from(v: Vec<u8>) -> Self { InfoValue::Bytes(v) } }
+impl From<Info> for InfoValue { fn from(v: Info) -> Self { InfoValue::Map(v) } }
 
 /// A frame returned by `Env::render`.
 #[derive(Clone, Debug, PartialEq)]
@@ -69,24 +155,163 @@ pub enum RenderFrame {
     },
 }
 
+/// How `Env::render` produces frames, chosen when the environment is constructed
+/// (Gymnasium's `render_mode`). Serializes with Gymnasium's names.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum RenderMode {
+    /// Rendering disabled; `render` returns `None`.
+    None,
+    /// `RenderFrame::Pixels` RGB frames.
+    #[default]
+    RgbArray,
+    /// `RenderFrame::Text` frames (ASCII art and a state readout).
+    Ansi,
+}
+
+impl RenderMode {
+    /// The Gymnasium name of the mode: "none", "rgb_array" or "ansi".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RenderMode::None => "none",
+            RenderMode::RgbArray => "rgb_array",
+            RenderMode::Ansi => "ansi",
+        }
+    }
+}
+
+impl std::fmt::Display for RenderMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl std::str::FromStr for RenderMode {
+    type Err = GymError;
+
+    /// Parse a Gymnasium mode name; "text" is accepted as an alias of "ansi".
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "none" => Ok(RenderMode::None),
+            "rgb_array" => Ok(RenderMode::RgbArray),
+            "ansi" | "text" => Ok(RenderMode::Ansi),
+            other => Err(GymError::NotSupported(format!("unknown render mode: {other}"))),
+        }
+    }
+}
+
+/// Static environment metadata, mirroring Gymnasium's `metadata` dict.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvMetadata {
+    /// Render modes the environment can produce (`RenderMode::None` is always accepted).
+    pub render_modes: Vec<RenderMode>,
+    /// Intended playback rate of rendered frames.
+    pub render_fps: Option<u32>,
+    /// Default (width, height) of `RenderMode::RgbArray` frames.
+    pub frame_size: Option<(u32, u32)>,
+}
+
+impl EnvMetadata {
+    pub fn new(render_modes: &[RenderMode], render_fps: u32, frame_size: (u32, u32)) -> Self {
+        Self { render_modes: render_modes.to_vec(), render_fps: Some(render_fps), frame_size: Some(frame_size) }
+    }
+
+    /// Whether `mode` can be selected for this environment.
+    pub fn supports(&self, mode: RenderMode) -> bool {
+        mode == RenderMode::None || self.render_modes.contains(&mode)
+    }
+
+    /// Return `GymError::NotSupported` unless `mode` can be selected.
+    pub fn check_render_mode(&self, mode: RenderMode) -> Result<()> {
+        if self.supports(mode) {
+            Ok(())
+        } else {
+            Err(GymError::NotSupported(format!("render mode {mode} (supported: {:?})", self.render_modes)))
+        }
+    }
+}
+
+/// Scalar component of a reward: `f32` or `f64`. A scalar is itself a single-component reward.
+pub trait RewardScalar:
+    Reward<Scalar = Self>
+    + Copy
+    + Default
+    + PartialOrd
+    + Into<f64>
+    + std::ops::Add<Output = Self>
+    + std::ops::Mul<Output = Self>
+    + std::fmt::Debug
+{
+}
+
+impl RewardScalar for f32 {}
+impl RewardScalar for f64 {}
+
+/// Reward carried by a `Step`: a scalar or, for multi-objective environments, a vector of
+/// per-objective scalars (`[T; N]` or `Vec<T>`). Reward wrappers operate component-wise.
+pub trait Reward: Clone {
+    type Scalar: RewardScalar;
+
+    /// The reward components; a scalar reward has exactly one.
+    fn components(&self) -> &[Self::Scalar];
+
+    /// Mutable access to the reward components.
+    fn components_mut(&mut self) -> &mut [Self::Scalar];
+
+    /// Apply `f` to every component.
+    fn map_components<F: FnMut(Self::Scalar) -> Self::Scalar>(mut self, mut f: F) -> Self {
+        for c in self.components_mut() {
+            *c = f(*c);
+        }
+        self
+    }
+
+    /// Info representation of accumulated components: `F64Array` unless the reward is a scalar.
+    fn info_value(components: &[f64]) -> InfoValue { InfoValue::F64Array(components.to_vec()) }
+}
+
+macro_rules! scalar_reward {
+    ($($t:ty),*) => {$(
+        impl Reward for $t {
+            type Scalar = $t;
+            fn components(&self) -> &[$t] { std::slice::from_ref(self) }
+            fn components_mut(&mut self) -> &mut [$t] { std::slice::from_mut(self) }
+            fn info_value(components: &[f64]) -> InfoValue { InfoValue::F64(components[0]) }
+        }
+    )*};
+}
+
+scalar_reward!(f32, f64);
+
+impl<T: RewardScalar, const N: usize> Reward for [T; N] {
+    type Scalar = T;
+    fn components(&self) -> &[T] { self }
+    fn components_mut(&mut self) -> &mut [T] { self }
+}
+
+impl<T: RewardScalar> Reward for Vec<T> {
+    type Scalar = T;
+    fn components(&self) -> &[T] { self }
+    fn components_mut(&mut self) -> &mut [T] { self }
+}
+
 /// A step result from the environment.
 #[derive(Clone, Debug, PartialEq)]
-pub struct Step<Obs> {
+pub struct Step<Obs, R = f32> {
     pub observation: Obs,
-    pub reward: f32,
+    pub reward: R,
     pub terminated: bool,
     pub truncated: bool,
     pub info: Info,
 }
 
-impl<Obs> Step<Obs> {
-    pub fn new(observation: Obs, reward: f32, terminated: bool, truncated: bool, info: Info) -> Self {
+impl<Obs, R> Step<Obs, R> {
+    pub fn new(observation: Obs, reward: R, terminated: bool, truncated: bool, info: Info) -> Self {
         Self { observation, reward, terminated, truncated, info }
     }
 }
 
 /// Recoverable errors across Gymnasium APIs.
-#[derive(thiserror::Error, Debug)]
+#[derive(thiserror::Error, Clone, Debug)]
 pub enum GymError {
     #[error("Invalid action: {0}")]
     InvalidAction(String),
@@ -94,8 +319,17 @@ pub enum GymError {
     InvalidObservation(String),
     #[error("Environment not ready: {0}")]
     NotReady(String),
+    /// A value is not an element of a space; see `Space::validate`.
+    #[error("{0}")]
+    OutOfSpace(String),
     #[error("Operation not supported: {0}")]
     NotSupported(String),
+    /// A caller-supplied argument is invalid, e.g. malformed `ResetOptions`.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+    /// `Env::render` produced a frame that does not match the render mode or is malformed.
+    #[error("Invalid render frame: {0}")]
+    InvalidRender(String),
     #[error("Other error: {0}")]
     Other(String),
 }
@@ -103,21 +337,188 @@ pub enum GymError {
 /// Convenience alias for results using GymError.
 pub type Result<T> = std::result::Result<T, GymError>;
 
+/// Options for `Env::reset_with_options`, mirroring the `options` dict of Gymnasium's `reset`.
+/// The meaning of `low`/`high` and the layout of `state` are documented per environment.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResetOptions {
+    /// Lower bound of the initial-state sampling range.
+    pub low: Option<f32>,
+    /// Upper bound of the initial-state sampling range.
+    pub high: Option<f32>,
+    /// Exact initial state; takes precedence over `low`/`high` and skips sampling.
+    pub state: Option<Vec<f32>>,
+}
+
+impl ResetOptions {
+    /// Options that leave the environment defaults untouched.
+    pub fn new() -> Self { Self::default() }
+
+    /// Sample the initial state from [low, high] instead of the default range.
+    pub fn with_bounds(low: f32, high: f32) -> Self { Self { low: Some(low), high: Some(high), state: None } }
+
+    /// Start from exactly this state.
+    pub fn with_state<S: Into<Vec<f32>>>(state: S) -> Self { Self { low: None, high: None, state: Some(state.into()) } }
+
+    /// The sampling range, falling back to the given defaults for unset bounds.
+    /// Returns `GymError::InvalidArgument` unless the range is finite and non-empty.
+    pub fn bounds_or(&self, low: f32, high: f32) -> Result<(f32, f32)> {
+        let (low, high) = (self.low.unwrap_or(low), self.high.unwrap_or(high));
+        if !(low.is_finite() && high.is_finite()) {
+            return Err(GymError::InvalidArgument(format!("reset options: bounds [{low}, {high}] are not finite")));
+        }
+        if low > high {
+            return Err(GymError::InvalidArgument(format!("reset options: low ({low}) > high ({high})")));
+        }
+        Ok((low, high))
+    }
+
+    /// The exact initial state, if one was given.
+    /// Returns `GymError::InvalidArgument` unless it has exactly `N` finite components.
+    pub fn initial_state<const N: usize>(&self) -> Result<Option<[f32; N]>> {
+        let Some(s) = &self.state else { return Ok(None) };
+        let state: [f32; N] = s.as_slice().try_into().map_err(|_| {
+            GymError::InvalidArgument(format!("reset options: expected an initial state of {N} values, got {}", s.len()))
+        })?;
+        if let Some(v) = state.iter().find(|v| !v.is_finite()) {
+            return Err(GymError::InvalidArgument(format!("reset options: initial state has a non-finite value {v}")));
+        }
+        Ok(Some(state))
+    }
+}
+
+/// Return `GymError::InvalidAction` unless `action` belongs to `space`.
+pub fn check_action<S: Space>(space: &S, action: &S::Element) -> Result<()> {
+    space
+        .validate(action)
+        .map_err(|e| GymError::InvalidAction(format!("action is outside the action space: {e}")))
+}
+
+/// `try_step` of environments that track episode state: `GymError::NotReady` while `needs_reset`
+/// is set (before the first `reset` or after the episode ended), then `check_action`, then `step`.
+pub fn checked_step<E: Env>(env: &mut E, needs_reset: bool, action: E::Act) -> Result<Step<E::Obs, E::Reward>> {
+    if needs_reset {
+        let name = std::any::type_name::<E>().rsplit("::").next().unwrap_or("env");
+        return Err(GymError::NotReady(format!("{name}: call reset() before stepping a new episode")));
+    }
+    check_action(&env.action_space(), &action)?;
+    Ok(env.step(action))
+}
+
 /// Core environment trait following the Gymnasium contract.
 pub trait Env {
     type Obs;
     type Act;
+    /// Space describing valid observations.
+    type ObsSpace: Space<Element = Self::Obs>;
+    /// Space describing valid actions.
+    type ActSpace: Space<Element = Self::Act>;
+    /// Reward type: `f32` for the built-in single-objective envs, a vector for multi-objective ones.
+    type Reward: Reward;
+
+    /// The space every observation returned by `reset`/`step` belongs to.
+    fn observation_space(&self) -> Self::ObsSpace;
+
+    /// The space of actions accepted by `step`.
+    fn action_space(&self) -> Self::ActSpace;
 
     /// Reset the environment to an initial state.
     /// Implementations should re-seed internal RNGs when `seed` is provided.
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info);
 
+    /// Reset with Gymnasium-style options (custom initial-state bounds or an exact initial state).
+    /// The default implementation ignores the options. Environments that honour them panic on
+    /// invalid options; use `try_reset_with_options` to get the error instead.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        let _ = options;
+        self.reset(seed)
+    }
+
     /// Apply an action and advance the environment by one step.
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs>;
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward>;
+
+    /// Checked variant of `reset`. The default implementation never fails.
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { Ok(self.reset(seed)) }
+
+    /// Checked variant of `reset_with_options`: invalid options are `GymError::InvalidArgument`.
+    /// The default implementation never fails.
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        Ok(self.reset_with_options(seed, options))
+    }
+
+    /// Checked variant of `step`.
+    /// Rejects actions outside `action_space()` with `GymError::InvalidAction`. Environments that
+    /// track episode state should also return `GymError::NotReady` when stepped before `reset`.
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        check_action(&self.action_space(), &action)?;
+        Ok(self.step(action))
+    }
+
+    /// Static metadata (supported render modes, fps, frame size). Defaults to no rendering support.
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::default() }
+
+    /// The render mode selected at construction.
+    fn render_mode(&self) -> RenderMode { RenderMode::None }
 
-    /// Render a frame of the current state, if supported.
+    /// Render a frame of the current state according to `render_mode()`, if supported.
     fn render(&self) -> Option<RenderFrame> { None }
 
+    /// Checked variant of `render`: `GymError::NotReady` when the env cannot render yet (e.g.
+    /// `OrderEnforcing` before `reset`). The default implementation never fails.
+    fn try_render(&self) -> Result<Option<RenderFrame>> { Ok(self.render()) }
+
     /// Close and release any external resources.
     fn close(&mut self) {}
 }
+
+/// Environments whose complete internal state can be captured and restored, e.g. for lookahead
+/// planning (MCTS) without cloning the whole environment.
+/// Restoring a snapshot must make subsequent `step`/`reset` calls reproduce the original
+/// trajectory exactly, including RNG draws.
+pub trait StatefulEnv: Env {
+    type State: Clone;
+
+    /// Capture the physics state, episode counters and RNG position.
+    fn get_state(&self) -> Self::State;
+
+    /// Restore a snapshot previously returned by `get_state`.
+    fn set_state(&mut self, state: &Self::State);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn info_typed_getters() {
+        let mut legs = Info::new();
+        legs.insert("contacts", InfoValue::from(vec![1i64, 0]));
+        let mut info = Info::new();
+        info.insert("forces", InfoValue::from(&[0.5f32, -1.0][..]));
+        info.insert("mask", InfoValue::from(vec![1u8, 0, 1]));
+        info.insert("legs", InfoValue::from(legs));
+        info.insert("ok", InfoValue::from(true));
+
+        assert_eq!(info.get_f64_array("forces"), Some(&[0.5, -1.0][..]));
+        assert_eq!(info.get_bytes("mask"), Some(&[1, 0, 1][..]));
+        assert_eq!(info.get_map("legs").and_then(|m| m.get_i64_array("contacts")), Some(&[1, 0][..]));
+        assert_eq!(info.get_bool("ok"), Some(true));
+        assert_eq!(info.get_f64("ok"), None);
+        assert_eq!(info.get_str("missing"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn info_serde_roundtrip_preserves_order() {
+        let mut nested = Info::new();
+        nested.insert("x", InfoValue::from(1.5));
+        let mut info = Info::new();
+        info.insert("z", InfoValue::from("last"));
+        info.insert("a", InfoValue::from(nested));
+        info.insert("ids", InfoValue::from(vec![3i64, 4]));
+
+        let json = serde_json::to_string(&info).unwrap();
+        assert_eq!(json, r#"{"z":{"Str":"last"},"a":{"Map":{"x":{"F64":1.5}}},"ids":{"I64Array":[3,4]}}"#);
+        let back: Info = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, info);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::core::{Env, Info, RenderFrame, Step};
-use crate::utils::render2d::{Canvas, BLACK, BLUE, GRAY, GREEN, RED, WHITE};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
+use crate::utils::render2d::{Canvas, BLACK, BLUE, GRAY, GREEN, WHITE};
 use crate::utils::rng::{rng_from_seed, RngStream};
+use crate::utils::ascii::{to_cell, TextCanvas};
 use rand::distributions::Distribution;
 
 /// A lightweight, dependency-free approximation of Gymnasium's LunarLander-v2
@@ -21,6 +25,7 @@ pub struct LunarLanderEnv {
     right_contact: bool,
 
     steps: u32,
+    needs_reset: bool,
     pub max_episode_steps: u32,
 
     // Simulation parameters
@@ -39,6 +44,23 @@ pub struct LunarLanderEnv {
     pad_half_width: f32,
 
     rng: RngStream,
+    render_mode: RenderMode,
+}
+
+/// Snapshot of a `LunarLanderEnv` (lander pose, velocities and leg contacts, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct LunarLanderState {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    pub angle: f32,
+    pub vang: f32,
+    pub left_contact: bool,
+    pub right_contact: bool,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
 }
 
 impl Default for LunarLanderEnv { fn default() -> Self { Self::new(2025) } }
@@ -55,6 +77,7 @@ impl LunarLanderEnv {
             left_contact: false,
             right_contact: false,
             steps: 0,
+            needs_reset: true,
             max_episode_steps: 1000,
             gravity: 0.6,
             main_thrust: 1.2,
@@ -66,10 +89,11 @@ impl LunarLanderEnv {
             y_limit: 1.5,
             pad_half_width: 0.2,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
         }
     }
 
-    fn obs(&self) -> [f32; 8] {
+    pub(crate) fn obs(&self) -> [f32; 8] {
         [
             self.x,
             self.y,
@@ -99,6 +123,67 @@ impl LunarLanderEnv {
             || self.y < -0.2
     }
 
+    pub(crate) fn needs_reset(&self) -> bool { self.needs_reset }
+
+    /// Integrate one physics step; returns (terminated, truncated).
+    pub(crate) fn advance(&mut self, action: u32) -> (bool, bool) {
+        // physics integration
+        let dt = self.dt;
+        // Apply gravity
+        self.vy -= self.gravity * dt;
+        // Apply action effects
+        self.apply_action(action.min(3));
+
+        // Damping
+        self.vx *= 1.0 - self.lin_damp;
+        self.vy *= 1.0 - self.lin_damp * 0.5;
+        self.vang *= 1.0 - self.ang_damp;
+
+        // Integrate
+        self.x += self.vx * dt;
+        self.y += self.vy * dt;
+        self.angle += self.vang * dt;
+        // keep angle in [-pi, pi]
+        let pi = std::f32::consts::PI; let two_pi = 2.0 * pi;
+        self.angle = ((self.angle + pi) % two_pi + two_pi) % two_pi - pi;
+
+        // Ground collision and contacts
+        self.left_contact = false; self.right_contact = false;
+        if self.y <= 0.0 {
+            // Detect a basic two-point contact from body x offset
+            let left_on_pad = (self.x - 0.05).abs() <= self.pad_half_width;
+            let right_on_pad = (self.x + 0.05).abs() <= self.pad_half_width;
+            self.left_contact = left_on_pad;
+            self.right_contact = right_on_pad;
+            // Clamp at ground
+            self.y = 0.0;
+            self.vy = 0.0;
+        }
+
+        self.steps += 1;
+        let terminated = self.landed_success() || self.crashed();
+        let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
+        (terminated, truncated)
+    }
+
+    /// Shaping term: penalties for distance to the pad center, speed and tilt.
+    pub(crate) fn shaping_reward(&self) -> f32 {
+        let mut reward = 0.0;
+        reward -= (self.x / self.x_limit).abs() * 0.5;
+        reward -= (self.vx.abs() + self.vy.abs()) * 0.1;
+        reward -= self.angle.abs() * 0.2;
+        reward
+    }
+
+    /// Outcome term: +100 for a successful landing, -100 for a crash.
+    pub(crate) fn landing_reward(&self) -> f32 {
+        let mut reward = 0.0;
+        if self.landed_success() { reward += 100.0; }
+        if self.crashed() { reward -= 100.0; }
+        reward
+    }
+
     fn apply_action(&mut self, action: u32) {
         // Convert action into forces/torques. Angle 0 is upright; +angle rotates clockwise.
         let dt = self.dt;
@@ -124,6 +209,42 @@ impl LunarLanderEnv {
         }
     }
 
+    /// Compact ASCII frame: ground, landing pad and lander (legs marked when in contact),
+    /// followed by a state readout.
+    pub fn render_text(&self) -> String {
+        let (w, h) = (41, 12);
+        let mut canvas = TextCanvas::new(w, h);
+        let col = |x: f32| to_cell(x, -self.x_limit, self.x_limit, w);
+        let row = |y: f32| h as i32 - 1 - to_cell(y, -0.2, self.y_limit, h);
+        let ground = row(0.0);
+        canvas.hline(0, w as i32 - 1, ground, '_');
+        canvas.hline(col(-self.pad_half_width), col(self.pad_half_width), ground, '=');
+        let (cx, cy) = (col(self.x), row(self.y).min(ground - 1));
+        canvas.put(cx, cy, 'A');
+        canvas.put(cx - 1, cy, if self.left_contact { 'L' } else { '/' });
+        canvas.put(cx + 1, cy, if self.right_contact { 'R' } else { '\\' });
+        canvas.render(&format!(
+            "x={:+.3} y={:+.3} vx={:+.3} vy={:+.3} angle={:+.3} vang={:+.3} legs={}{} step={}",
+            self.x,
+            self.y,
+            self.vx,
+            self.vy,
+            self.angle,
+            self.vang,
+            u8::from(self.left_contact),
+            u8::from(self.right_contact),
+            self.steps
+        ))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("LunarLanderEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
         let mut canvas = Canvas::new(width.max(400), height.max(300));
         let w = canvas.width as i32; let h = canvas.height as i32;
@@ -181,76 +302,109 @@ impl LunarLanderEnv {
 impl Env for LunarLanderEnv {
     type Obs = [f32; 8];
     type Act = u32; // 0..=3
+    type ObsSpace = BoxSpace<f32, 8>;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        // Same bounds as Gymnasium's LunarLander observation space.
+        let pi = std::f32::consts::PI;
+        BoxSpace::new(
+            [-2.5, -2.5, -10.0, -10.0, -2.0 * pi, -10.0, 0.0, 0.0],
+            [2.5, 2.5, 10.0, 10.0, 2.0 * pi, 10.0, 1.0, 1.0],
+        )
+    }
+
+    fn action_space(&self) -> Self::ActSpace { Discrete::new(4) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the initial horizontal position (default ±0.05; the tilt stays
+    /// within ±0.1); `state` sets `[x, y, vx, vy, angle, angular_velocity]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-0.05, 0.05)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        use rand::distributions::Uniform;
-        let uni_x = Uniform::new_inclusive(-0.05f32, 0.05f32);
-        let uni_a = Uniform::new_inclusive(-0.1f32, 0.1f32);
-        self.x = uni_x.sample(&mut self.rng);
-        self.y = 1.2; // start above ground
-        self.vx = 0.0;
-        self.vy = 0.0;
-        self.angle = uni_a.sample(&mut self.rng);
-        self.vang = 0.0;
+        if let Some([x, y, vx, vy, angle, vang]) = state {
+            (self.x, self.y, self.vx, self.vy, self.angle, self.vang) = (x, y, vx, vy, angle, vang);
+        } else {
+            use rand::distributions::Uniform;
+            let uni_x = Uniform::new_inclusive(low, high);
+            let uni_a = Uniform::new_inclusive(-0.1f32, 0.1f32);
+            self.x = uni_x.sample(&mut self.rng);
+            self.y = 1.2; // start above ground
+            self.vx = 0.0;
+            self.vy = 0.0;
+            self.angle = uni_a.sample(&mut self.rng);
+            self.vang = 0.0;
+        }
         self.left_contact = false;
         self.right_contact = false;
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
-        // physics integration
-        let dt = self.dt;
-        // Apply gravity
-        self.vy -= self.gravity * dt;
-        // Apply action effects
-        self.apply_action(action.min(3));
+        let (terminated, truncated) = self.advance(action);
+        // Reward shaping: distance to pad center, penalty for tilt and speed; big reward for success
+        let reward = self.shaping_reward() + self.landing_reward();
+        Step::new(self.obs(), reward, terminated, truncated, Info::new())
+    }
 
-        // Damping
-        self.vx *= 1.0 - self.lin_damp;
-        self.vy *= 1.0 - self.lin_damp * 0.5;
-        self.vang *= 1.0 - self.ang_damp;
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
 
-        // Integrate
-        self.x += self.vx * dt;
-        self.y += self.vy * dt;
-        self.angle += self.vang * dt;
-        // keep angle in [-pi, pi]
-        let pi = std::f32::consts::PI; let two_pi = 2.0 * pi;
-        self.angle = ((self.angle + pi) % two_pi + two_pi) % two_pi - pi;
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 50, (400, 300)) }
 
-        // Ground collision and contacts
-        self.left_contact = false; self.right_contact = false;
-        if self.y <= 0.0 {
-            // Detect a basic two-point contact from body x offset
-            let left_on_pad = (self.x - 0.05).abs() <= self.pad_half_width;
-            let right_on_pad = (self.x + 0.05).abs() <= self.pad_half_width;
-            self.left_contact = left_on_pad;
-            self.right_contact = right_on_pad;
-            // Clamp at ground
-            self.y = 0.0;
-            self.vy = 0.0;
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
+    fn render(&self) -> Option<RenderFrame> {
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
         }
+    }
 
-        self.steps += 1;
-        let terminated = self.landed_success() || self.crashed();
-        let truncated = self.steps >= self.max_episode_steps;
+    fn close(&mut self) {}
+}
 
-        // Reward shaping: distance to pad center, penalty for tilt and speed; big reward for success
-        let mut reward = 0.0;
-        reward -= (self.x / self.x_limit).abs() * 0.5;
-        reward -= (self.vx.abs() + self.vy.abs()) * 0.1;
-        reward -= self.angle.abs() * 0.2;
-        if self.landed_success() { reward += 100.0; }
-        if self.crashed() { reward -= 100.0; }
+impl StatefulEnv for LunarLanderEnv {
+    type State = LunarLanderState;
 
-        Step::new(self.obs(), reward, terminated, truncated, Info::new())
+    fn get_state(&self) -> Self::State {
+        LunarLanderState {
+            x: self.x,
+            y: self.y,
+            vx: self.vx,
+            vy: self.vy,
+            angle: self.angle,
+            vang: self.vang,
+            left_contact: self.left_contact,
+            right_contact: self.right_contact,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
     }
 
-    fn render(&self) -> Option<RenderFrame> {
-        Some(self.render_pixels(400, 300))
+    fn set_state(&mut self, state: &Self::State) {
+        self.x = state.x;
+        self.y = state.y;
+        self.vx = state.vx;
+        self.vy = state.vy;
+        self.angle = state.angle;
+        self.vang = state.vang;
+        self.left_contact = state.left_contact;
+        self.right_contact = state.right_contact;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
     }
-
-    fn close(&mut self) {}
 }
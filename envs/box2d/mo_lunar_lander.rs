@@ -0,0 +1,84 @@
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
+
+use super::lunar_lander::{LunarLanderEnv, LunarLanderState};
+
+/// Multi-objective variant of `LunarLanderEnv`, in the spirit of MO-Gymnasium's
+/// `mo-lunar-lander`. Dynamics, observations and actions are identical; the reward is a vector
+/// `[landing, shaping, main_fuel, side_fuel]`:
+/// - landing: +100 on a successful landing, -100 on a crash, 0 otherwise
+/// - shaping: distance/speed/tilt penalties of the scalar environment
+/// - main_fuel: -1 for every step the main engine fires
+/// - side_fuel: -1 for every step a side engine fires
+///
+/// The scalar `LunarLanderEnv` reward equals `landing + shaping` (weights `[1, 1, 0, 0]`).
+pub struct MoLunarLanderEnv {
+    inner: LunarLanderEnv,
+}
+
+impl Default for MoLunarLanderEnv { fn default() -> Self { Self::new(2025) } }
+
+impl From<LunarLanderEnv> for MoLunarLanderEnv {
+    fn from(inner: LunarLanderEnv) -> Self { Self { inner } }
+}
+
+impl MoLunarLanderEnv {
+    pub fn new(seed: u64) -> Self { Self { inner: LunarLanderEnv::new(seed) } }
+
+    /// See `LunarLanderEnv::with_render_mode`.
+    pub fn with_render_mode(self, mode: RenderMode) -> Self { Self { inner: self.inner.with_render_mode(mode) } }
+
+    /// Number of reward objectives.
+    pub const NUM_OBJECTIVES: usize = 4;
+}
+
+impl Env for MoLunarLanderEnv {
+    type Obs = [f32; 8];
+    type Act = u32; // 0..=3
+    type ObsSpace = BoxSpace<f32, 8>;
+    type ActSpace = Discrete;
+    type Reward = [f32; 4];
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
+
+    fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
+
+    /// Same options as `LunarLanderEnv::reset_with_options`.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let (terminated, truncated) = self.inner.advance(action);
+        // `advance` treats actions above 3 as 3 (right engine)
+        let action = action.min(3);
+        let main_fuel = if action == 2 { -1.0 } else { 0.0 };
+        let side_fuel = if action == 1 || action == 3 { -1.0 } else { 0.0 };
+        let reward = [self.inner.landing_reward(), self.inner.shaping_reward(), main_fuel, side_fuel];
+        Step::new(self.inner.obs(), reward, terminated, truncated, Info::new())
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        checked_step(self, self.inner.needs_reset(), action)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
+    fn render(&self) -> Option<RenderFrame> { self.inner.render() }
+
+    fn close(&mut self) {}
+}
+
+impl StatefulEnv for MoLunarLanderEnv {
+    type State = LunarLanderState;
+
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
@@ -1,3 +1,5 @@
 pub mod lunar_lander;
+pub mod mo_lunar_lander;
 
-pub use lunar_lander::LunarLanderEnv;
+pub use lunar_lander::{LunarLanderEnv, LunarLanderState};
+pub use mo_lunar_lander::MoLunarLanderEnv;
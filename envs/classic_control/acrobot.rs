@@ -1,6 +1,10 @@
-use crate::core::{Env, Info, RenderFrame, Step};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
 use crate::utils::rng::{rng_from_seed, RngStream};
-use crate::utils::render2d::{Canvas, Color, BLACK, BLUE, GRAY, GREEN, RED, WHITE};
+use crate::utils::ascii::TextCanvas;
+use crate::utils::render2d::{Canvas, BLUE, GRAY, GREEN, RED, WHITE};
 use rand::distributions::Distribution;
 
 /// Acrobot-v1 environment (Gymnasium classic_control)
@@ -16,9 +20,11 @@ pub struct AcrobotEnv {
     dth2: f32,
 
     steps: u32,
+    needs_reset: bool,
     pub max_episode_steps: u32,
 
     rng: RngStream,
+    render_mode: RenderMode,
 
     // Constants (following Gymnasium)
     m1: f32,
@@ -27,8 +33,8 @@ pub struct AcrobotEnv {
     l2: f32,
     lc1: f32,
     lc2: f32,
-    I1: f32,
-    I2: f32,
+    i1: f32,
+    i2: f32,
     g: f32,
     dt: f32, // integration timestep
 
@@ -37,6 +43,18 @@ pub struct AcrobotEnv {
     max_vel_2: f32,
 }
 
+/// Snapshot of a `AcrobotEnv` (joint angles and angular velocities, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcrobotState {
+    pub th1: f32,
+    pub th2: f32,
+    pub dth1: f32,
+    pub dth2: f32,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
+}
+
 impl Default for AcrobotEnv {
     fn default() -> Self { Self::new(7) }
 }
@@ -49,16 +67,18 @@ impl AcrobotEnv {
             dth1: 0.0,
             dth2: 0.0,
             steps: 0,
+            needs_reset: true,
             max_episode_steps: 500,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
             m1: 1.0,
             m2: 1.0,
             l1: 1.0,
             l2: 1.0,
             lc1: 0.5,
             lc2: 0.5,
-            I1: 1.0,
-            I2: 1.0,
+            i1: 1.0,
+            i2: 1.0,
             g: 9.8,
             dt: 0.2,
             max_vel_1: 4.0 * std::f32::consts::PI,
@@ -74,6 +94,40 @@ impl AcrobotEnv {
         h >= 1.0
     }
 
+    /// Compact ASCII frame: both links hanging from the pivot and the goal height, followed by a
+    /// state readout.
+    pub fn render_text(&self) -> String {
+        let (w, h) = (33, 13);
+        let mut canvas = TextCanvas::new(w, h);
+        let (px, py) = (w as i32 / 2, h as i32 / 2);
+        // Both links together span half the grid height; columns are twice as dense as rows.
+        let sy = (h / 2) as f32 / (self.l1 + self.l2);
+        let sx = 2.0 * sy;
+        let cell = |x: f32, y: f32| (px + (x * sx).round() as i32, py + (y * sy).round() as i32);
+        let (x1, y1) = (self.l1 * self.th1.sin(), self.l1 * self.th1.cos());
+        let th12 = self.th1 + self.th2;
+        let (x2, y2) = (x1 + self.l2 * th12.sin(), y1 + self.l2 * th12.cos());
+        // Goal: the tip rises one link length above the pivot.
+        canvas.hline(0, w as i32 - 1, py - sy.round() as i32, '-');
+        let (c1, c2) = (cell(x1, y1), cell(x2, y2));
+        canvas.draw_line(px, py, c1.0, c1.1, '#');
+        canvas.draw_line(c1.0, c1.1, c2.0, c2.1, '*');
+        canvas.put(c1.0, c1.1, 'o');
+        canvas.put(px, py, 'O');
+        canvas.render(&format!(
+            "theta1={:+.3} theta2={:+.3} dtheta1={:+.3} dtheta2={:+.3} step={}",
+            self.th1, self.th2, self.dth1, self.dth2, self.steps
+        ))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("AcrobotEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
         let mut canvas = Canvas::new(width.max(400), height.max(400));
         canvas.clear(WHITE);
@@ -125,19 +179,19 @@ impl AcrobotEnv {
     fn dynamics(&self, th1: f32, th2: f32, dth1: f32, dth2: f32, torque: f32) -> (f32, f32) {
         // Equations adapted from Gymnasium's acrobot implementation
         let m1 = self.m1; let m2 = self.m2;
-        let l1 = self.l1; let l2 = self.l2;
+        let l1 = self.l1;
         let lc1 = self.lc1; let lc2 = self.lc2;
-        let I1 = self.I1; let I2 = self.I2;
+        let i1 = self.i1; let i2 = self.i2;
         let g = self.g;
-        let d1 = m1 * lc1 * lc1 + m2 * (l1 * l1 + lc2 * lc2 + 2.0 * l1 * lc2 * (th2).cos()) + I1 + I2;
-        let d2 = m2 * (lc2 * lc2 + l1 * lc2 * (th2).cos()) + I2;
+        let d1 = m1 * lc1 * lc1 + m2 * (l1 * l1 + lc2 * lc2 + 2.0 * l1 * lc2 * (th2).cos()) + i1 + i2;
+        let d2 = m2 * (lc2 * lc2 + l1 * lc2 * (th2).cos()) + i2;
         let phi2 = m2 * lc2 * g * (th1 + th2 - std::f32::consts::FRAC_PI_2).cos();
         let phi1 = -m2 * l1 * lc2 * dth2 * dth2 * (th2).sin()
             - 2.0 * m2 * l1 * lc2 * dth2 * dth1 * (th2).sin()
             + (m1 * lc1 + m2 * l1) * g * (th1 - std::f32::consts::FRAC_PI_2).cos()
             + phi2;
         let ddth2 = (torque + d2 / d1 * phi1 - m2 * l1 * lc2 * dth1 * dth1 * (th2).sin() - phi2)
-            / (m2 * lc2 * lc2 + I2 - d2 * d2 / d1);
+            / (m2 * lc2 * lc2 + i2 - d2 * d2 / d1);
         let ddth1 = -(d2 * ddth2 + phi1) / d1;
         (ddth1, ddth2)
     }
@@ -146,19 +200,50 @@ impl AcrobotEnv {
 impl Env for AcrobotEnv {
     type Obs = [f32; 4];
     type Act = u32; // 0,1,2
+    type ObsSpace = BoxSpace<f32, 4>;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        let pi = std::f32::consts::PI;
+        BoxSpace::new(
+            [-pi, -pi, -self.max_vel_1, -self.max_vel_2],
+            [pi, pi, self.max_vel_1, self.max_vel_2],
+        )
+    }
+
+    fn action_space(&self) -> Self::ActSpace { Discrete::new(3) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the initial joint angles (default [-pi, pi]); the angular
+    /// velocities are always drawn from [-0.1, 0.1], use `state` to set them.
+    /// `state` sets `[theta1, theta2, dtheta1, dtheta2]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let pi = std::f32::consts::PI;
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-pi, pi)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        // Initialize th1, th2 uniformly in [-pi, pi], velocities in [-0.1, 0.1]
-        use rand::distributions::Uniform;
-        let mut u_ang = Uniform::new_inclusive(-std::f32::consts::PI, std::f32::consts::PI);
-        let mut u_vel = Uniform::new_inclusive(-0.1f32, 0.1f32);
-        self.th1 = u_ang.sample(&mut self.rng);
-        self.th2 = u_ang.sample(&mut self.rng);
-        self.dth1 = u_vel.sample(&mut self.rng);
-        self.dth2 = u_vel.sample(&mut self.rng);
+        if let Some([th1, th2, dth1, dth2]) = state {
+            (self.th1, self.th2, self.dth1, self.dth2) = (th1, th2, dth1, dth2);
+        } else {
+            use rand::distributions::Uniform;
+            let u_ang = Uniform::new_inclusive(low, high);
+            let u_vel = Uniform::new_inclusive(-0.1f32, 0.1f32);
+            self.th1 = u_ang.sample(&mut self.rng);
+            self.th2 = u_ang.sample(&mut self.rng);
+            self.dth1 = u_vel.sample(&mut self.rng);
+            self.dth2 = u_vel.sample(&mut self.rng);
+        }
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
@@ -207,13 +292,50 @@ impl Env for AcrobotEnv {
         self.steps += 1;
         let terminated = self.terminal_height_reached();
         let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
         let reward = if terminated { 0.0 } else { -1.0 };
         Step::new(self.obs(), reward, terminated, truncated, Info::new())
     }
 
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
+
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 15, (400, 400)) }
+
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
     fn render(&self) -> Option<RenderFrame> {
-        Some(self.render_pixels(400, 400))
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
+        }
     }
 
     fn close(&mut self) {}
 }
+
+impl StatefulEnv for AcrobotEnv {
+    type State = AcrobotState;
+
+    fn get_state(&self) -> Self::State {
+        AcrobotState {
+            th1: self.th1,
+            th2: self.th2,
+            dth1: self.dth1,
+            dth2: self.dth2,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.th1 = state.th1;
+        self.th2 = state.th2;
+        self.dth1 = state.dth1;
+        self.dth2 = state.dth2;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
+    }
+}
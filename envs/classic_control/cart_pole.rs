@@ -1,5 +1,9 @@
-use crate::core::{Env, Info, RenderFrame, Step};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
 use crate::utils::rng::{rng_from_seed, RngStream};
+use crate::utils::ascii::{to_cell, TextCanvas};
 use crate::utils::render2d::{Canvas, GRAY, WHITE, BLACK, BEIGE, MAUVE};
 use rand::distributions::Distribution;
 
@@ -16,14 +20,15 @@ pub struct CartPoleEnv {
 
     // Episode management
     steps: u32,
+    needs_reset: bool,
     pub max_episode_steps: u32,
 
     // RNG
     rng: RngStream,
+    render_mode: RenderMode,
 
     // Physics constants (from Gymnasium)
     gravity: f32,        // 9.8
-    masscart: f32,       // 1.0
     masspole: f32,       // 0.1
     total_mass: f32,     // masscart + masspole
     length: f32,         // actually half the pole's length (0.5)
@@ -36,6 +41,18 @@ pub struct CartPoleEnv {
     x_threshold: f32,             // 2.4
 }
 
+/// Snapshot of a `CartPoleEnv` (cart position/velocity and pole angle/angular velocity, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CartPoleState {
+    pub x: f32,
+    pub x_dot: f32,
+    pub theta: f32,
+    pub theta_dot: f32,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
+}
+
 impl Default for CartPoleEnv {
     fn default() -> Self { Self::new(1_234_567) }
 }
@@ -58,10 +75,11 @@ impl CartPoleEnv {
             theta: 0.0,
             theta_dot: 0.0,
             steps: 0,
+            needs_reset: true,
             max_episode_steps: 500,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
             gravity,
-            masscart,
             masspole,
             total_mass,
             length,
@@ -73,6 +91,34 @@ impl CartPoleEnv {
         }
     }
 
+    /// Compact ASCII frame: track, cart and pole, followed by a state readout.
+    pub fn render_text(&self) -> String {
+        let (w, pole_rows) = (41, 3);
+        let mut canvas = TextCanvas::new(w, pole_rows + 3);
+        let track = pole_rows as i32 + 2;
+        canvas.hline(0, w as i32 - 1, track, '=');
+        let cx = to_cell(self.x, -self.x_threshold, self.x_threshold, w);
+        canvas.text(cx - 1, track - 1, "[_]");
+        // Columns are drawn twice as dense as rows to roughly preserve the aspect ratio.
+        let pivot = track - 2;
+        let ex = cx + (self.theta.sin() * pole_rows as f32 * 2.0).round() as i32;
+        let ey = pivot - (self.theta.cos() * pole_rows as f32).round() as i32;
+        let pole = if self.theta.abs() < 0.2 { '|' } else if self.theta > 0.0 { '/' } else { '\\' };
+        canvas.draw_line(cx, pivot, ex, ey, pole);
+        canvas.render(&format!(
+            "x={:+.3} x_dot={:+.3} theta={:+.3} theta_dot={:+.3} step={}",
+            self.x, self.x_dot, self.theta, self.theta_dot, self.steps
+        ))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("CartPoleEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     /// Returns a 2D pixel rendering of the current state with the requested size.
     /// This does not open a window; it just produces an RGBA pixel buffer.
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
@@ -107,7 +153,7 @@ impl CartPoleEnv {
         let pole_len_px = (2.0 * self.length * scale).max(1.0);
         let top_x = cart_cx as i32;
         let top_y = cart_y; // attach at cart top center
-        let theta = self.theta as f32;
+        let theta = self.theta;
         let end_x = top_x + (theta.sin() * pole_len_px) as i32;
         let end_y = top_y - (theta.cos() * pole_len_px) as i32;
 
@@ -148,17 +194,45 @@ impl CartPoleEnv {
 impl Env for CartPoleEnv {
     type Obs = [f32; 4];
     type Act = u32; // 0 or 1
+    type ObsSpace = BoxSpace<f32, 4>;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        // Positions are bounded at twice the termination thresholds; velocities are unbounded.
+        let high = [self.x_threshold * 2.0, f32::INFINITY, self.theta_threshold_radians * 2.0, f32::INFINITY];
+        BoxSpace::new(high.map(|v| -v), high)
+    }
+
+    fn action_space(&self) -> Self::ActSpace { Discrete::new(2) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the uniform noise of all four state variables (default ±0.05);
+    /// `state` sets `[x, x_dot, theta, theta_dot]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-0.05, 0.05)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        // small uniform noise in [-0.05, 0.05]
-        let mut uni = rand::distributions::Uniform::new_inclusive(-0.05f32, 0.05f32);
-        self.x = uni.sample(&mut self.rng);
-        self.x_dot = uni.sample(&mut self.rng);
-        self.theta = uni.sample(&mut self.rng);
-        self.theta_dot = uni.sample(&mut self.rng);
+        if let Some([x, x_dot, theta, theta_dot]) = state {
+            (self.x, self.x_dot, self.theta, self.theta_dot) = (x, x_dot, theta, theta_dot);
+        } else {
+            // small uniform noise in [-0.05, 0.05] by default
+            let uni = rand::distributions::Uniform::new_inclusive(low, high);
+            self.x = uni.sample(&mut self.rng);
+            self.x_dot = uni.sample(&mut self.rng);
+            self.theta = uni.sample(&mut self.rng);
+            self.theta_dot = uni.sample(&mut self.rng);
+        }
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
@@ -182,15 +256,50 @@ impl Env for CartPoleEnv {
         self.steps += 1;
         let terminated = self.terminated();
         let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
         let reward = 1.0;
         Step::new(self.obs(), reward, terminated, truncated, Info::new())
     }
 
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
+
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 50, (320, 240)) }
+
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
     fn render(&self) -> Option<RenderFrame> {
-        // Return a 2D pixel rendering by default.
-        // Choose a reasonable default canvas size.
-        Some(self.render_pixels(320, 240))
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
+        }
     }
 
     fn close(&mut self) {}
 }
+
+impl StatefulEnv for CartPoleEnv {
+    type State = CartPoleState;
+
+    fn get_state(&self) -> Self::State {
+        CartPoleState {
+            x: self.x,
+            x_dot: self.x_dot,
+            theta: self.theta,
+            theta_dot: self.theta_dot,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.x = state.x;
+        self.x_dot = state.x_dot;
+        self.theta = state.theta;
+        self.theta_dot = state.theta_dot;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
+    }
+}
@@ -4,8 +4,8 @@ pub mod mountain_car_continuous;
 pub mod acrobot;
 pub mod pendulum;
 
-pub use cart_pole::CartPoleEnv;
-pub use mountain_car::MountainCarEnv;
-pub use mountain_car_continuous::MountainCarContinuousEnv;
-pub use acrobot::AcrobotEnv;
-pub use pendulum::PendulumEnv;
+pub use cart_pole::{CartPoleEnv, CartPoleState};
+pub use mountain_car::{MountainCarEnv, MountainCarState};
+pub use mountain_car_continuous::{MountainCarContinuousEnv, MountainCarContinuousState};
+pub use acrobot::{AcrobotEnv, AcrobotState};
+pub use pendulum::{PendulumEnv, PendulumState};
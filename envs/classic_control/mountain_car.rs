@@ -1,6 +1,10 @@
-use crate::core::{Env, Info, RenderFrame, Step};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
 use crate::utils::rng::{rng_from_seed, RngStream};
-use crate::utils::render2d::{Canvas, BLACK, BLUE, GRAY, GREEN, RED, WHITE};
+use crate::utils::ascii::{to_cell, TextCanvas};
+use crate::utils::render2d::{Canvas, BLUE, GRAY, GREEN, RED, WHITE};
 use rand::distributions::Distribution;
 
 /// MountainCar-v0 environment (Gymnasium classic_control)
@@ -13,8 +17,10 @@ pub struct MountainCarEnv {
 
     pub max_episode_steps: u32,
     steps: u32,
+    needs_reset: bool,
 
     rng: RngStream,
+    render_mode: RenderMode,
 
     // Constants
     min_position: f32, // -1.2
@@ -25,6 +31,16 @@ pub struct MountainCarEnv {
     gravity: f32,      // 0.0025
 }
 
+/// Snapshot of a `MountainCarEnv` (car position and velocity, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MountainCarState {
+    pub position: f32,
+    pub velocity: f32,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
+}
+
 impl Default for MountainCarEnv {
     fn default() -> Self { Self::new(2024) }
 }
@@ -36,7 +52,9 @@ impl MountainCarEnv {
             velocity: 0.0,
             max_episode_steps: 200,
             steps: 0,
+            needs_reset: true,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
             min_position: -1.2,
             max_position: 0.6,
             max_speed: 0.07,
@@ -48,6 +66,22 @@ impl MountainCarEnv {
 
     fn obs(&self) -> [f32; 2] { [self.position, self.velocity] }
 
+    /// Compact ASCII frame: hill profile, goal flag and car, followed by a state readout.
+    pub fn render_text(&self) -> String {
+        mountain_text(self.position, self.min_position, self.max_position, self.goal_position).render(&format!(
+            "position={:+.3} velocity={:+.4} step={}",
+            self.position, self.velocity, self.steps
+        ))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("MountainCarEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     /// Produce a simple 2D pixel rendering similar in spirit to Gymnasium's MountainCar.
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
         let mut canvas = Canvas::new(width.max(320), height.max(240));
@@ -68,7 +102,7 @@ impl MountainCarEnv {
         let usable = (w - 2 * margin) as f32;
         let to_screen_x = |xw: f32| -> i32 {
             let t = ((xw - world_min) / (world_max - world_min)).clamp(0.0, 1.0);
-            margin as i32 + (t * usable) as i32
+            margin + (t * usable) as i32
         };
         let terrain_y = |xw: f32| -> i32 {
             // Gym uses cos in dynamics; the terrain shape usually is y = sin(3x)
@@ -112,18 +146,60 @@ impl MountainCarEnv {
     }
 }
 
+/// ASCII hill profile (y = sin(3x)) with the goal flag and the car; shared with
+/// `MountainCarContinuousEnv`.
+pub(crate) fn mountain_text(position: f32, min_position: f32, max_position: f32, goal_position: f32) -> TextCanvas {
+    let (w, h) = (41, 9);
+    let mut canvas = TextCanvas::new(w, h);
+    let col = |x: f32| to_cell(x, min_position, max_position, w);
+    let row = |x: f32| 1 + to_cell(-(3.0 * x).sin(), -1.0, 1.0, h - 1);
+    for c in 0..w {
+        let x = min_position + (max_position - min_position) * c as f32 / (w - 1) as f32;
+        canvas.put(c as i32, row(x), '.');
+    }
+    canvas.put(col(goal_position), row(goal_position) - 1, 'F');
+    canvas.put(col(position), row(position) - 1, 'C');
+    canvas
+}
+
 impl Env for MountainCarEnv {
     type Obs = [f32; 2];
     type Act = u32; // 0,1,2
+    type ObsSpace = BoxSpace<f32, 2>;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        BoxSpace::new([self.min_position, -self.max_speed], [self.max_position, self.max_speed])
+    }
+
+    fn action_space(&self) -> Self::ActSpace { Discrete::new(3) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the initial position (default [-0.6, -0.4]; velocity starts at 0);
+    /// `state` sets `[position, velocity]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-0.6, -0.4)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        // sample initial position uniformly in [-0.6, -0.4], velocity=0
-        let mut uni = rand::distributions::Uniform::new_inclusive(-0.6f32, -0.4f32);
-        self.position = uni.sample(&mut self.rng);
-        self.velocity = 0.0;
+        if let Some([position, velocity]) = state {
+            (self.position, self.velocity) = (position, velocity);
+        } else {
+            // sample initial position uniformly in [-0.6, -0.4] by default, velocity=0
+            let uni = rand::distributions::Uniform::new_inclusive(low, high);
+            self.position = uni.sample(&mut self.rng);
+            self.velocity = 0.0;
+        }
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
@@ -140,14 +216,46 @@ impl Env for MountainCarEnv {
         self.steps += 1;
         let terminated = self.position >= self.goal_position;
         let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
         let reward = if terminated { 0.0 } else { -1.0 };
         Step::new(self.obs(), reward, terminated, truncated, Info::new())
     }
 
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
+
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 30, (320, 240)) }
+
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
     fn render(&self) -> Option<RenderFrame> {
-        // Return a 2D pixel rendering by default with a reasonable canvas size.
-        Some(self.render_pixels(320, 240))
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
+        }
     }
 
     fn close(&mut self) {}
 }
+
+impl StatefulEnv for MountainCarEnv {
+    type State = MountainCarState;
+
+    fn get_state(&self) -> Self::State {
+        MountainCarState {
+            position: self.position,
+            velocity: self.velocity,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.position = state.position;
+        self.velocity = state.velocity;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
+    }
+}
@@ -1,11 +1,15 @@
-use crate::core::{Env, Info, RenderFrame, Step};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::BoxSpace;
 use crate::utils::rng::{rng_from_seed, RngStream};
+use super::mountain_car::mountain_text;
 use crate::utils::render2d::{Canvas, BLUE, GRAY, GREEN, RED, WHITE};
 use rand::distributions::Distribution;
 
 /// MountainCarContinuous-v0 environment (Gymnasium classic_control)
 /// Observation: [position, velocity]
-/// Action space: Box([-1.0], [1.0]) -> scalar throttle in [-1, 1]
+/// Action space: Box([-1.0], [1.0]) -> one-element throttle in [-1, 1]
 /// Reward: -0.1 * action^2 each step; +100 bonus and terminate when reaching the goal.
 /// Episode truncates at max steps (default 999)
 pub struct MountainCarContinuousEnv {
@@ -14,8 +18,10 @@ pub struct MountainCarContinuousEnv {
 
     pub max_episode_steps: u32,
     steps: u32,
+    needs_reset: bool,
 
     rng: RngStream,
+    render_mode: RenderMode,
 
     // Constants (matching Gymnasium defaults)
     min_position: f32, // -1.2
@@ -26,6 +32,16 @@ pub struct MountainCarContinuousEnv {
     gravity: f32,      // 0.0025
 }
 
+/// Snapshot of a `MountainCarContinuousEnv` (car position and velocity, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MountainCarContinuousState {
+    pub position: f32,
+    pub velocity: f32,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
+}
+
 impl Default for MountainCarContinuousEnv { fn default() -> Self { Self::new(2024) } }
 
 impl MountainCarContinuousEnv {
@@ -35,7 +51,9 @@ impl MountainCarContinuousEnv {
             velocity: 0.0,
             max_episode_steps: 999,
             steps: 0,
+            needs_reset: true,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
             min_position: -1.2,
             max_position: 0.6,
             max_speed: 0.07,
@@ -47,6 +65,22 @@ impl MountainCarContinuousEnv {
 
     fn obs(&self) -> [f32; 2] { [self.position, self.velocity] }
 
+    /// Compact ASCII frame: hill profile, goal flag and car, followed by a state readout.
+    pub fn render_text(&self) -> String {
+        mountain_text(self.position, self.min_position, self.max_position, self.goal_position).render(&format!(
+            "position={:+.3} velocity={:+.4} step={}",
+            self.position, self.velocity, self.steps
+        ))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("MountainCarContinuousEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     /// 2D pixel rendering similar to MountainCarEnv (discrete)
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
         let mut canvas = Canvas::new(width.max(320), height.max(240));
@@ -66,7 +100,7 @@ impl MountainCarContinuousEnv {
         let usable = (w - 2 * margin) as f32;
         let to_screen_x = |xw: f32| -> i32 {
             let t = ((xw - world_min) / (world_max - world_min)).clamp(0.0, 1.0);
-            margin as i32 + (t * usable) as i32
+            margin + (t * usable) as i32
         };
         let terrain_y = |xw: f32| -> i32 {
             let y = (3.0 * xw).sin(); // ~[-1, 1]
@@ -111,20 +145,47 @@ impl MountainCarContinuousEnv {
 
 impl Env for MountainCarContinuousEnv {
     type Obs = [f32; 2];
-    type Act = f32; // continuous scalar in [-1, 1]
+    type Act = [f32; 1]; // continuous throttle in [-1, 1]
+    type ObsSpace = BoxSpace<f32, 2>;
+    type ActSpace = BoxSpace<f32, 1>;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        BoxSpace::new([self.min_position, -self.max_speed], [self.max_position, self.max_speed])
+    }
+
+    fn action_space(&self) -> Self::ActSpace { BoxSpace::new([-1.0], [1.0]) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the initial position (default [-0.6, -0.4]; velocity starts at 0);
+    /// `state` sets `[position, velocity]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-0.6, -0.4)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        let mut uni = rand::distributions::Uniform::new_inclusive(-0.6f32, -0.4f32);
-        self.position = uni.sample(&mut self.rng);
-        self.velocity = 0.0;
+        if let Some([position, velocity]) = state {
+            (self.position, self.velocity) = (position, velocity);
+        } else {
+            // sample initial position uniformly in [-0.6, -0.4] by default, velocity=0
+            let uni = rand::distributions::Uniform::new_inclusive(low, high);
+            self.position = uni.sample(&mut self.rng);
+            self.velocity = 0.0;
+        }
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
         // Clip action to [-1, 1]
-        let a = action.clamp(-1.0, 1.0);
+        let a = action[0].clamp(-1.0, 1.0);
         // Dynamics as in Gymnasium MountainCarContinuous
         self.velocity += a * self.power - self.gravity * (3.0 * self.position).cos();
         if self.velocity > self.max_speed { self.velocity = self.max_speed; }
@@ -139,10 +200,45 @@ impl Env for MountainCarContinuousEnv {
         let terminated = self.position >= self.goal_position;
         if terminated { reward += 100.0; }
         let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
         Step::new(self.obs(), reward, terminated, truncated, Info::new())
     }
 
-    fn render(&self) -> Option<RenderFrame> { Some(self.render_pixels(320, 240)) }
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
+
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 30, (320, 240)) }
+
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
+    fn render(&self) -> Option<RenderFrame> {
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
+        }
+    }
 
     fn close(&mut self) {}
 }
+
+impl StatefulEnv for MountainCarContinuousEnv {
+    type State = MountainCarContinuousState;
+
+    fn get_state(&self) -> Self::State {
+        MountainCarContinuousState {
+            position: self.position,
+            velocity: self.velocity,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.position = state.position;
+        self.velocity = state.velocity;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
+    }
+}
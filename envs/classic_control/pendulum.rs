@@ -1,5 +1,9 @@
-use crate::core::{Env, Info, RenderFrame, Step};
+use crate::core::{
+    checked_step, Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step,
+};
+use crate::spaces::{BoxSpace, Discrete};
 use crate::utils::rng::{rng_from_seed, RngStream};
+use crate::utils::ascii::TextCanvas;
 use crate::utils::render2d::{Canvas, BLACK, BLUE, GRAY, RED, WHITE};
 use rand::distributions::Distribution;
 
@@ -14,8 +18,10 @@ pub struct PendulumEnv {
 
     pub max_episode_steps: u32,
     steps: u32,
+    needs_reset: bool,
 
     rng: RngStream,
+    render_mode: RenderMode,
 
     // constants
     g: f32,
@@ -26,6 +32,16 @@ pub struct PendulumEnv {
     dt: f32,
 }
 
+/// Snapshot of a `PendulumEnv` (pendulum angle and angular velocity, step counter and RNG position).
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendulumState {
+    pub theta: f32,
+    pub theta_dot: f32,
+    pub steps: u32,
+    pub needs_reset: bool,
+    pub rng: RngStream,
+}
+
 impl Default for PendulumEnv { fn default() -> Self { Self::new(42) } }
 
 impl PendulumEnv {
@@ -35,7 +51,9 @@ impl PendulumEnv {
             theta_dot: 0.0,
             max_episode_steps: 200,
             steps: 0,
+            needs_reset: true,
             rng: rng_from_seed(seed),
+            render_mode: RenderMode::default(),
             g: 10.0,
             m: 1.0,
             l: 1.0,
@@ -55,6 +73,29 @@ impl PendulumEnv {
         ((x + pi) % (2.0 * pi) + (2.0 * pi)) % (2.0 * pi) - pi
     }
 
+    /// Compact ASCII frame: rod and bob around the pivot (upright at theta = 0), followed by a
+    /// state readout.
+    pub fn render_text(&self) -> String {
+        let (w, h, rows) = (21, 11, 4.0f32);
+        let mut canvas = TextCanvas::new(w, h);
+        let (px, py) = (w as i32 / 2, h as i32 / 2);
+        // Columns are twice as dense as rows to roughly preserve the aspect ratio.
+        let bx = px + (self.theta.sin() * rows * 2.0).round() as i32;
+        let by = py - (self.theta.cos() * rows).round() as i32;
+        canvas.draw_line(px, py, bx, by, '*');
+        canvas.put(bx, by, 'o');
+        canvas.put(px, py, '+');
+        canvas.render(&format!("theta={:+.3} theta_dot={:+.3} step={}", self.theta, self.theta_dot, self.steps))
+    }
+
+    /// Select how `render` produces frames (default `RenderMode::RgbArray`; `Ansi` yields `render_text`).
+    /// Panics if the mode is not listed in `metadata().render_modes`.
+    pub fn with_render_mode(mut self, mode: RenderMode) -> Self {
+        if let Err(e) = self.metadata().check_render_mode(mode) { panic!("PendulumEnv: {e}"); }
+        self.render_mode = mode;
+        self
+    }
+
     /// Simple 2D rendering: draw a pivot and a rod with a bob.
     pub fn render_pixels(&self, width: u32, height: u32) -> RenderFrame {
         let mut canvas = Canvas::new(width.max(320), height.max(240));
@@ -93,16 +134,42 @@ impl PendulumEnv {
 impl Env for PendulumEnv {
     type Obs = [f32; 3];
     type Act = u32; // 0,1,2
+    type ObsSpace = BoxSpace<f32, 3>;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> Self::ObsSpace {
+        BoxSpace::new([-1.0, -1.0, -self.max_speed], [1.0, 1.0, self.max_speed])
+    }
+
+    fn action_space(&self) -> Self::ActSpace { Discrete::new(3) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.reset_with_options(seed, &ResetOptions::default())
+    }
+
+    /// Options: `low`/`high` bound the initial angle (default [-pi, pi]; angular velocity stays in
+    /// [-1, 1]); `state` sets `[theta, theta_dot]` exactly.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.try_reset_with_options(seed, options).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let state = options.initial_state()?;
+        let (low, high) = options.bounds_or(-std::f32::consts::PI, std::f32::consts::PI)?;
         if let Some(s) = seed { self.rng = rng_from_seed(s); }
-        // theta ~ U[-pi, pi), theta_dot ~ U[-1, 1]
-        let mut u_theta = rand::distributions::Uniform::new_inclusive(-std::f32::consts::PI, std::f32::consts::PI);
-        let mut u_vel = rand::distributions::Uniform::new_inclusive(-1.0f32, 1.0f32);
-        self.theta = u_theta.sample(&mut self.rng);
-        self.theta_dot = u_vel.sample(&mut self.rng);
+        if let Some([theta, theta_dot]) = state {
+            (self.theta, self.theta_dot) = (theta, theta_dot);
+        } else {
+            // theta ~ U[-pi, pi], theta_dot ~ U[-1, 1] by default
+            let u_theta = rand::distributions::Uniform::new_inclusive(low, high);
+            let u_vel = rand::distributions::Uniform::new_inclusive(-1.0f32, 1.0f32);
+            self.theta = u_theta.sample(&mut self.rng);
+            self.theta_dot = u_vel.sample(&mut self.rng);
+        }
         self.steps = 0;
-        (self.obs(), Info::new())
+        self.needs_reset = false;
+        Ok((self.obs(), Info::new()))
     }
 
     fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
@@ -124,10 +191,45 @@ impl Env for PendulumEnv {
         let reward = -cost;
         let terminated = false; // pendulum has no terminal condition in v1
         let truncated = self.steps >= self.max_episode_steps;
+        self.needs_reset = terminated || truncated;
         Step::new(self.obs(), reward, terminated, truncated, Info::new())
     }
 
-    fn render(&self) -> Option<RenderFrame> { Some(self.render_pixels(320, 240)) }
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs>> { checked_step(self, self.needs_reset, action) }
+
+    fn metadata(&self) -> EnvMetadata { EnvMetadata::new(&[RenderMode::RgbArray, RenderMode::Ansi], 30, (320, 240)) }
+
+    fn render_mode(&self) -> RenderMode { self.render_mode }
+
+    fn render(&self) -> Option<RenderFrame> {
+        match self.render_mode {
+            RenderMode::RgbArray => self.metadata().frame_size.map(|(w, h)| self.render_pixels(w, h)),
+            RenderMode::Ansi => Some(RenderFrame::Text(self.render_text())),
+            RenderMode::None => None,
+        }
+    }
 
     fn close(&mut self) {}
 }
+
+impl StatefulEnv for PendulumEnv {
+    type State = PendulumState;
+
+    fn get_state(&self) -> Self::State {
+        PendulumState {
+            theta: self.theta,
+            theta_dot: self.theta_dot,
+            steps: self.steps,
+            needs_reset: self.needs_reset,
+            rng: self.rng.clone(),
+        }
+    }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.theta = state.theta;
+        self.theta_dot = state.theta_dot;
+        self.steps = state.steps;
+        self.needs_reset = state.needs_reset;
+        self.rng = state.rng.clone();
+    }
+}
@@ -2,4 +2,5 @@ pub mod classic_control;
 pub mod box2d;
 
 pub use classic_control::{CartPoleEnv, MountainCarEnv, MountainCarContinuousEnv, AcrobotEnv, PendulumEnv};
-pub use box2d::LunarLanderEnv;
+pub use classic_control::{AcrobotState, CartPoleState, MountainCarContinuousState, MountainCarState, PendulumState};
+pub use box2d::{LunarLanderEnv, LunarLanderState, MoLunarLanderEnv};
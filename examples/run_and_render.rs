@@ -1,4 +1,4 @@
-use rust_gymnasium::{AcrobotEnv, CartPoleEnv, Env, MountainCarEnv, PendulumEnv, RenderFrame};
+use rust_gymnasium::{CartPoleEnv, Env, RenderFrame};
 use minifb::{Key, Window, WindowOptions};
 use rand::Rng;
 
@@ -46,13 +46,8 @@ fn main() {
         frame = env.render().expect("render() should produce a frame");
         if let RenderFrame::Pixels { width, height, data } = frame {
             // Convert RGBA bytes to ARGB u32s for minifb
-            for i in 0..(width as usize * height as usize) {
-                let idx = i * 4;
-                let r = data[idx];
-                let g = data[idx + 1];
-                let b = data[idx + 2];
-                let a = data[idx + 3];
-                buffer[i] = rgba_to_u32(a, r, g, b);
+            for (px, rgba) in buffer.iter_mut().zip(data.chunks_exact(4)) {
+                *px = rgba_to_u32(rgba[3], rgba[0], rgba[1], rgba[2]);
             }
             window.update_with_buffer(&buffer, width as usize, height as usize)
                 .expect("Failed to update window buffer");
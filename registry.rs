@@ -1,12 +1,14 @@
 // Registration and Specs (README Step 8)
-/// Minimal registry system to construct environments by id with associated EnvSpec.
+// Minimal registry system to construct environments by id with associated EnvSpec.
 
 use std::any::Any;
 use std::collections::HashMap;
 use std::sync::{OnceLock, RwLock};
 
-use crate::core::{Env, Info, RenderFrame, Result, Step};
+use crate::core::{Env, EnvMetadata, Info, RenderFrame, RenderMode, ResetOptions, Result, StatefulEnv, Step};
 use crate::core::GymError;
+use crate::spaces::{DynConvert, DynSpace, DynValue};
+use crate::wrappers::{OrderEnforcing, PassiveEnvChecker};
 
 /// Key-value kwargs for make(). Keep simple for now: stringly-typed values.
 pub type KwArgs = HashMap<String, String>;
@@ -23,10 +25,14 @@ pub struct EnvSpec {
     pub reward_threshold: Option<f32>,
     /// Whether environment has inherent nondeterminism beyond RNG seed.
     pub nondeterministic: bool,
-    /// Whether to enforce order between reset/step/etc.
+    /// Whether make() wraps the env in `OrderEnforcing` (step/render before reset is an error).
     pub order_enforce: bool,
+    /// Whether make() skips the `PassiveEnvChecker` wrapper.
+    pub disable_env_checker: bool,
     /// Version string or semver-like number (free-form for now).
     pub version: Option<String>,
+    /// Render modes, fps and frame size of the environment (see `Env::metadata`).
+    pub metadata: EnvMetadata,
 }
 
 impl EnvSpec {
@@ -37,47 +43,176 @@ impl EnvSpec {
             reward_threshold: None,
             nondeterministic: false,
             order_enforce: true,
+            disable_env_checker: false,
             version: None,
+            metadata: EnvMetadata::default(),
         }
     }
 }
 
 /// A type-erased environment trait to allow Box<dyn EnvDyn> results from make().
+/// Observations and actions are untyped `DynValue`s living in the `DynSpace`s below, so generic
+/// tooling (random agents, loggers, serializers) can drive any registered env.
 pub trait EnvDyn {
-    fn reset(&mut self, seed: Option<u64>) -> (Box<dyn Any>, Info);
-    fn step(&mut self, action: Box<dyn Any>) -> Step<Box<dyn Any>>;
+    /// Untyped form of `Env::observation_space`.
+    fn observation_space(&self) -> DynSpace;
+    /// Untyped form of `Env::action_space`.
+    fn action_space(&self) -> DynSpace;
+    fn reset(&mut self, seed: Option<u64>) -> (DynValue, Info);
+    /// Reset with Gymnasium-style options; see `Env::reset_with_options`.
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (DynValue, Info);
+    /// Panics if the action does not convert to the env's action type.
+    fn step(&mut self, action: DynValue) -> Step<DynValue>;
+    /// Checked reset; see `Env::try_reset`.
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(DynValue, Info)>;
+    /// Checked reset with options; see `Env::try_reset_with_options`.
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(DynValue, Info)>;
+    /// Checked step: an action of the wrong kind or shape yields `GymError::InvalidAction` instead of a panic.
+    fn try_step(&mut self, action: DynValue) -> Result<Step<DynValue>>;
+    fn metadata(&self) -> EnvMetadata;
+    fn render_mode(&self) -> RenderMode;
     fn render(&self) -> Option<RenderFrame>;
+    /// Checked render; see `Env::try_render`.
+    fn try_render(&self) -> Result<Option<RenderFrame>>;
     fn close(&mut self);
+    /// Violations recorded by the `PassiveEnvChecker` that `make` applied, in the order they were
+    /// found; see `PassiveEnvChecker::warnings`. Empty when the checker is disabled.
+    fn env_checker_warnings(&self) -> Vec<GymError> { Vec::new() }
+    /// Snapshot the environment state (see `StatefulEnv`); `NotSupported` unless registered
+    /// through `factory_of_stateful`.
+    fn get_state(&self) -> Result<Box<dyn Any>> {
+        Err(GymError::NotSupported("environment does not support state snapshots".into()))
+    }
+    /// Restore a snapshot produced by `get_state` on an environment of the same type.
+    fn set_state(&mut self, _state: &dyn Any) -> Result<()> {
+        Err(GymError::NotSupported("environment does not support state snapshots".into()))
+    }
+}
+
+/// Type-erased `StatefulEnv` accessors, present when the env was registered as stateful.
+struct StateFns<E> {
+    get: fn(&E) -> Box<dyn Any>,
+    set: fn(&mut E, &dyn Any) -> Result<()>,
+}
+
+fn get_state_any<E: StatefulEnv>(env: &E) -> Box<dyn Any>
+where
+    E::State: 'static,
+{
+    Box::new(env.get_state())
+}
+
+fn set_state_any<E: StatefulEnv>(env: &mut E, state: &dyn Any) -> Result<()>
+where
+    E::State: 'static,
+{
+    let state = state.downcast_ref::<E::State>().ok_or_else(|| {
+        GymError::Other(format!("expected state of type {}", std::any::type_name::<E::State>()))
+    })?;
+    env.set_state(state);
+    Ok(())
 }
 
-/// Wrapper to adapt any Env into EnvDyn by boxing Obs/Act via Any.
-struct DynEnv<E: Env>(E);
+fn checker_warnings<E: Env>(env: &PassiveEnvChecker<E>) -> Vec<GymError> { env.warnings().to_vec() }
 
-impl<E: Env> EnvDyn for DynEnv<E>
+fn ordered_checker_warnings<E: Env>(env: &OrderEnforcing<PassiveEnvChecker<E>>) -> Vec<GymError> {
+    checker_warnings(env.inner())
+}
+
+/// Wrapper to adapt any Env into EnvDyn by converting Obs/Act to and from `DynValue`.
+struct DynEnv<E: Env> {
+    env: E,
+    state_fns: Option<StateFns<E>>,
+    /// Reads the warnings of the `PassiveEnvChecker` inside `env`, if there is one.
+    checker_warnings: Option<fn(&E) -> Vec<GymError>>,
+}
+
+impl<E: Env> DynEnv<E>
 where
-    E::Obs: Any + 'static,
-    E::Act: Any + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
 {
-    fn reset(&mut self, seed: Option<u64>) -> (Box<dyn Any>, Info) {
-        let (obs, info) = self.0.reset(seed);
-        (Box::new(obs), info)
+    fn observation(&self, obs: E::Obs) -> Result<DynValue> {
+        self.env.observation_space().to_dyn_value(obs).map_err(|e| GymError::InvalidObservation(e.to_string()))
     }
 
-    fn step(&mut self, action: Box<dyn Any>) -> Step<Box<dyn Any>> {
-        let action = *action
-            .downcast::<E::Act>()
-            .map_err(|_| ())
-            .expect("invalid action type for DynEnv");
-        let s = self.0.step(action);
-        Step::new(Box::new(s.observation) as Box<dyn Any>, s.reward, s.terminated, s.truncated, s.info)
+    fn action(&self, action: DynValue) -> Result<E::Act> {
+        self.env.action_space().element_from_dyn(action).map_err(|e| GymError::InvalidAction(e.to_string()))
     }
 
-    fn render(&self) -> Option<RenderFrame> { self.0.render() }
-    fn close(&mut self) { self.0.close() }
+    fn erase_step(&self, s: Step<E::Obs, f32>) -> Result<Step<DynValue>> {
+        Ok(Step::new(self.observation(s.observation)?, s.reward, s.terminated, s.truncated, s.info))
+    }
+}
+
+impl<E: Env<Reward = f32>> EnvDyn for DynEnv<E>
+where
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+{
+    fn observation_space(&self) -> DynSpace { self.env.observation_space().to_dyn_space() }
+    fn action_space(&self) -> DynSpace { self.env.action_space().to_dyn_space() }
+
+    fn reset(&mut self, seed: Option<u64>) -> (DynValue, Info) {
+        let (obs, info) = self.env.reset(seed);
+        (self.observation(obs).unwrap_or_else(|e| panic!("{e}")), info)
+    }
+
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (DynValue, Info) {
+        let (obs, info) = self.env.reset_with_options(seed, options);
+        (self.observation(obs).unwrap_or_else(|e| panic!("{e}")), info)
+    }
+
+    fn step(&mut self, action: DynValue) -> Step<DynValue> {
+        let action = self.action(action).unwrap_or_else(|e| panic!("{e}"));
+        let s = self.env.step(action);
+        self.erase_step(s).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(DynValue, Info)> {
+        let (obs, info) = self.env.try_reset(seed)?;
+        Ok((self.observation(obs)?, info))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(DynValue, Info)> {
+        let (obs, info) = self.env.try_reset_with_options(seed, options)?;
+        Ok((self.observation(obs)?, info))
+    }
+
+    fn try_step(&mut self, action: DynValue) -> Result<Step<DynValue>> {
+        let action = self.action(action)?;
+        let s = self.env.try_step(action)?;
+        self.erase_step(s)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.env.metadata() }
+    fn render_mode(&self) -> RenderMode { self.env.render_mode() }
+    fn render(&self) -> Option<RenderFrame> { self.env.render() }
+    fn try_render(&self) -> Result<Option<RenderFrame>> { self.env.try_render() }
+    fn close(&mut self) { self.env.close() }
+
+    fn env_checker_warnings(&self) -> Vec<GymError> {
+        self.checker_warnings.map(|f| f(&self.env)).unwrap_or_default()
+    }
+
+    fn get_state(&self) -> Result<Box<dyn Any>> {
+        match &self.state_fns {
+            Some(fns) => Ok((fns.get)(&self.env)),
+            None => Err(GymError::NotSupported("environment does not support state snapshots".into())),
+        }
+    }
+
+    fn set_state(&mut self, state: &dyn Any) -> Result<()> {
+        match &self.state_fns {
+            Some(fns) => (fns.set)(&mut self.env, state),
+            None => Err(GymError::NotSupported("environment does not support state snapshots".into())),
+        }
+    }
 }
 
 /// Factory closure type for constructing environments with kwargs.
-pub type FactoryFn = Box<dyn Fn(KwArgs) -> Box<dyn EnvDyn + Send + Sync> + Send + Sync>;
+/// It receives the registered spec so it can apply the wrappers the spec asks for.
+pub type FactoryFn = Box<dyn Fn(&EnvSpec, KwArgs) -> Box<dyn EnvDyn + Send + Sync> + Send + Sync>;
 
 #[derive(Default)]
 struct RegistryInner {
@@ -92,6 +227,14 @@ struct Registry {
 impl Registry {
     fn new() -> Self { Self { inner: RwLock::new(RegistryInner::default()) } }
 
+    fn with_builtins() -> Self {
+        let registry = Self::new();
+        for (spec, factory) in builtin_envs() {
+            registry.register(spec, factory).expect("built-in env ids are unique");
+        }
+        registry
+    }
+
     fn register(&self, spec: EnvSpec, factory: FactoryFn) -> Result<()> {
         let mut g = self.inner.write().map_err(|_| GymError::Other("registry poisoned".into()))?;
         if g.specs.contains_key(&spec.id) {
@@ -109,8 +252,14 @@ impl Registry {
 
     fn make(&self, id: &str, kwargs: KwArgs) -> Result<Box<dyn EnvDyn + Send + Sync>> {
         let guard = self.inner.read().map_err(|_| GymError::Other("registry poisoned".into()))?;
-        match guard.factories.get(id) {
-            Some(f) => Ok((f)(kwargs)),
+        // Reject unsupported `render_mode` kwargs for envs that declare their render modes.
+        if let Some(spec) = guard.specs.get(id).filter(|s| !s.metadata.render_modes.is_empty())
+            && kwargs.contains_key("render_mode")
+        {
+            spec.metadata.check_render_mode(render_mode_kwarg(&kwargs)?)?;
+        }
+        match guard.specs.get(id).zip(guard.factories.get(id)) {
+            Some((spec, f)) => Ok((f)(spec, kwargs)),
             None => Err(GymError::Other(format!("Unknown environment id: {}", id)))
         }
     }
@@ -119,7 +268,7 @@ impl Registry {
 static REGISTRY: OnceLock<Registry> = OnceLock::new();
 
 fn registry() -> &'static Registry {
-    REGISTRY.get_or_init(|| Registry::new())
+    REGISTRY.get_or_init(Registry::with_builtins)
 }
 
 /// Register an environment spec and its factory globally.
@@ -129,32 +278,120 @@ pub fn register(spec: EnvSpec, factory: FactoryFn) -> Result<()> { registry().re
 pub fn get_spec(id: &str) -> Option<EnvSpec> { registry().get_spec(id) }
 
 /// Construct an environment by id with kwargs, returning a boxed dynamic env.
+/// As in Gymnasium, the env is wrapped in `PassiveEnvChecker` unless `spec.disable_env_checker`,
+/// then in `OrderEnforcing` if `spec.order_enforce`.
 pub fn make<S: AsRef<str>>(id: S, kwargs: KwArgs) -> Result<Box<dyn EnvDyn + Send + Sync>> { registry().make(id.as_ref(), kwargs) }
 
+fn erase<E>(env: E, checker_warnings: Option<fn(&E) -> Vec<GymError>>) -> Box<dyn EnvDyn + Send + Sync>
+where
+    E: Env<Reward = f32> + Send + Sync + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+{
+    Box::new(DynEnv::<E> { env, state_fns: None, checker_warnings })
+}
+
+fn erase_stateful<E>(env: E, checker_warnings: Option<fn(&E) -> Vec<GymError>>) -> Box<dyn EnvDyn + Send + Sync>
+where
+    E: StatefulEnv<Reward = f32> + Send + Sync + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+    E::State: 'static,
+{
+    let state_fns = StateFns { get: get_state_any::<E>, set: set_state_any::<E> };
+    Box::new(DynEnv::<E> { env, state_fns: Some(state_fns), checker_warnings })
+}
+
+/// Wrap `$env` in `PassiveEnvChecker`/`OrderEnforcing` as `$spec` asks, then type-erase it with
+/// `$erase`. Each combination is a distinct type, hence a macro rather than a function.
+macro_rules! erase_with_spec_wrappers {
+    ($spec:expr, $env:expr, $erase:ident) => {
+        match ($spec.order_enforce, $spec.disable_env_checker) {
+            (true, false) => $erase(OrderEnforcing::new(PassiveEnvChecker::new($env)), Some(ordered_checker_warnings)),
+            (true, true) => $erase(OrderEnforcing::new($env), None),
+            (false, false) => $erase(PassiveEnvChecker::new($env), Some(checker_warnings)),
+            (false, true) => $erase($env, None),
+        }
+    };
+}
+
 /// Helper to adapt a concrete Env into a factory function easily.
+/// Type-erased environments report scalar `f32` rewards; wrap multi-objective envs in
+/// `LinearReward` before registering them.
 pub fn factory_of<E, F>(ctor: F) -> FactoryFn
 where
-    E: Env + Send + Sync + 'static,
-    E::Obs: Any + 'static,
-    E::Act: Any + 'static,
+    E: Env<Reward = f32> + Send + Sync + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+    F: Fn(KwArgs) -> E + Send + Sync + 'static,
+{
+    Box::new(move |spec: &EnvSpec, kwargs: KwArgs| erase_with_spec_wrappers!(spec, ctor(kwargs), erase))
+}
+
+/// Like `factory_of`, additionally exposing `StatefulEnv` snapshots through `EnvDyn::get_state`/`set_state`.
+pub fn factory_of_stateful<E, F>(ctor: F) -> FactoryFn
+where
+    E: StatefulEnv<Reward = f32> + Send + Sync + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+    E::State: 'static,
     F: Fn(KwArgs) -> E + Send + Sync + 'static,
 {
-    Box::new(move |kwargs: KwArgs| {
-        let env = ctor(kwargs);
-        Box::new(DynEnv::<E>(env)) as Box<dyn EnvDyn + Send + Sync>
-    })
+    Box::new(move |spec: &EnvSpec, kwargs: KwArgs| erase_with_spec_wrappers!(spec, ctor(kwargs), erase_stateful))
+}
+
+/// Parse the `render_mode` kwarg ("rgb_array", "ansi" or "none"); `RgbArray` when absent.
+pub fn render_mode_kwarg(kwargs: &KwArgs) -> Result<RenderMode> {
+    kwargs.get("render_mode").map_or(Ok(RenderMode::RgbArray), |m| m.parse())
+}
+
+/// Spec and factory for a built-in env; the factory honours the `render_mode` kwarg.
+fn builtin<E, F>(id: &str, max_episode_steps: u32, reward_threshold: Option<f32>, ctor: F) -> (EnvSpec, FactoryFn)
+where
+    E: StatefulEnv<Reward = f32> + Send + Sync + 'static,
+    E::ObsSpace: DynConvert,
+    E::ActSpace: DynConvert,
+    E::State: 'static,
+    F: Fn(RenderMode) -> E + Send + Sync + 'static,
+{
+    let mut spec = EnvSpec::new(id);
+    spec.max_episode_steps = Some(max_episode_steps);
+    spec.reward_threshold = reward_threshold;
+    spec.metadata = ctor(RenderMode::None).metadata();
+    // make() validates the kwarg against the spec before calling the factory.
+    let factory = factory_of_stateful(move |kwargs| ctor(render_mode_kwarg(&kwargs).unwrap_or_default()));
+    (spec, factory)
+}
+
+/// Gymnasium ids of the built-in environments, registered on first use of the registry.
+fn builtin_envs() -> Vec<(EnvSpec, FactoryFn)> {
+    use crate::envs::*;
+    vec![
+        builtin("CartPole-v1", 500, Some(475.0), |m| CartPoleEnv::default().with_render_mode(m)),
+        builtin("MountainCar-v0", 200, Some(-110.0), |m| MountainCarEnv::default().with_render_mode(m)),
+        builtin("MountainCarContinuous-v0", 999, Some(90.0), |m| MountainCarContinuousEnv::default().with_render_mode(m)),
+        builtin("Acrobot-v1", 500, Some(-100.0), |m| AcrobotEnv::default().with_render_mode(m)),
+        builtin("Pendulum-v1", 200, None, |m| PendulumEnv::default().with_render_mode(m)),
+        builtin("LunarLander-v3", 1000, Some(200.0), |m| LunarLanderEnv::default().with_render_mode(m)),
+    ]
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::{Env, Step};
+    use crate::spaces::Discrete;
 
     #[derive(Default)]
     struct Dummy;
     impl Env for Dummy {
-        type Obs = i32;
-        type Act = i32;
+        type Obs = u32;
+        type Act = u32;
+        type ObsSpace = Discrete;
+        type ActSpace = Discrete;
+        type Reward = f32;
+        fn observation_space(&self) -> Self::ObsSpace { Discrete::new(10) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(10) }
         fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) { (0, Info::new()) }
         fn step(&mut self, a: Self::Act) -> Step<Self::Obs> { Step::new(a, 0.0, true, false, Info::new()) }
         fn render(&self) -> Option<RenderFrame> { Some(RenderFrame::Text("dummy".into())) }
@@ -163,13 +400,136 @@ mod tests {
 
     #[test]
     fn register_and_make_dummy() {
-        let spec = EnvSpec { id: "Dummy-v0".into(), max_episode_steps: Some(10), reward_threshold: None, nondeterministic: false, order_enforce: true, version: Some("0".into()) };
-        register(spec.clone(), factory_of::<Dummy, _>(|_k| Dummy::default())).expect("register ok");
+        let spec = EnvSpec { id: "Dummy-v0".into(), max_episode_steps: Some(10), reward_threshold: None, nondeterministic: false, order_enforce: true, disable_env_checker: false, version: Some("0".into()), metadata: EnvMetadata::default() };
+        register(spec.clone(), factory_of::<Dummy, _>(|_k| Dummy)).expect("register ok");
         let mut env = make("Dummy-v0", KwArgs::new()).expect("make ok");
         let (obs, _info) = env.reset(None);
-        assert!(obs.downcast_ref::<i32>().is_some());
-        let s = env.step(Box::new(5));
-        assert!(s.observation.downcast_ref::<i32>() == Some(&5));
+        assert_eq!(obs, DynValue::Int(0));
+        let s = env.step(DynValue::Int(5));
+        assert_eq!(s.observation, DynValue::Int(5));
+        assert_eq!(env.observation_space(), DynSpace::Discrete(Discrete::new(10)));
         assert!(matches!(env.render(), Some(RenderFrame::Text(_))));
     }
+
+    #[test]
+    fn dyn_state_roundtrip() {
+        use crate::envs::CartPoleEnv;
+        register(EnvSpec::new("CartPoleStateful-v0"), factory_of_stateful(|_k| CartPoleEnv::default())).expect("register ok");
+        let mut env = make("CartPoleStateful-v0", KwArgs::new()).expect("make ok");
+        env.reset(Some(3));
+        let snapshot = env.get_state().expect("stateful");
+        let first = env.step(DynValue::Int(1));
+        env.set_state(snapshot.as_ref()).expect("restore");
+        let again = env.step(DynValue::Int(1));
+        assert_eq!(first.observation, again.observation);
+        assert!(env.set_state(&0u8).is_err());
+
+        register(EnvSpec::new("DummyStateless-v0"), factory_of::<Dummy, _>(|_k| Dummy)).expect("register ok");
+        let env = make("DummyStateless-v0", KwArgs::new()).expect("make ok");
+        assert!(matches!(env.get_state(), Err(GymError::NotSupported(_))));
+    }
+
+    #[test]
+    fn dyn_try_step_reports_errors() {
+        let spec = EnvSpec::new("DummyChecked-v0");
+        register(spec, factory_of::<Dummy, _>(|_k| Dummy)).expect("register ok");
+        let mut env = make("DummyChecked-v0", KwArgs::new()).expect("make ok");
+        env.try_reset(Some(0)).expect("reset ok");
+        assert!(matches!(env.try_step(DynValue::Floats(vec![5.0])), Err(GymError::InvalidAction(_))));
+        assert!(matches!(env.try_step(DynValue::Int(-1)), Err(GymError::InvalidAction(_))));
+        assert!(matches!(env.try_step(DynValue::Int(42)), Err(GymError::InvalidAction(_))));
+        let s = env.try_step(DynValue::Int(5)).expect("valid action");
+        assert_eq!(s.observation, DynValue::Int(5));
+    }
+
+    #[test]
+    fn builtin_envs_honour_render_mode_kwarg() {
+        let spec = get_spec("CartPole-v1").expect("built-in");
+        assert_eq!(spec.metadata.render_fps, Some(50));
+        assert_eq!(spec.metadata.render_modes, vec![RenderMode::RgbArray, RenderMode::Ansi]);
+
+        let mut env = make("CartPole-v1", KwArgs::new()).expect("make ok");
+        env.reset(Some(0));
+        assert_eq!(env.render_mode(), RenderMode::RgbArray);
+        assert!(matches!(env.render(), Some(RenderFrame::Pixels { width: 320, height: 240, .. })));
+
+        let kwargs = KwArgs::from([("render_mode".to_string(), "none".to_string())]);
+        let mut env = make("LunarLander-v3", kwargs).expect("make ok");
+        env.reset(Some(0));
+        assert_eq!(env.render_mode(), RenderMode::None);
+        assert!(env.render().is_none());
+
+        let kwargs = KwArgs::from([("render_mode".to_string(), "human".to_string())]);
+        assert!(make("Acrobot-v1", kwargs).is_err());
+    }
+
+    /// Returns observations outside its observation space.
+    struct OutOfSpace;
+    impl Env for OutOfSpace {
+        type Obs = u32;
+        type Act = u32;
+        type ObsSpace = Discrete;
+        type ActSpace = Discrete;
+        type Reward = f32;
+        fn observation_space(&self) -> Self::ObsSpace { Discrete::new(2) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(2) }
+        fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) { (7, Info::new()) }
+        fn step(&mut self, _a: Self::Act) -> Step<Self::Obs> { Step::new(7, 0.0, false, false, Info::new()) }
+    }
+
+    #[test]
+    fn make_applies_spec_wrappers() {
+        let mut env = make("CartPole-v1", KwArgs::new()).expect("make ok");
+        assert!(env.render().is_none());
+        assert!(matches!(env.try_render(), Err(GymError::NotReady(_))));
+        assert!(matches!(env.try_step(DynValue::Int(0)), Err(GymError::NotReady(_))));
+        env.try_reset(Some(0)).expect("reset ok");
+        assert!(env.try_step(DynValue::Int(0)).is_ok());
+        assert!(env.render().is_some());
+        assert!(matches!(env.try_render(), Ok(Some(_))));
+        assert!(env.env_checker_warnings().is_empty());
+
+        register(EnvSpec::new("OutOfSpace-v0"), factory_of(|_k| OutOfSpace)).expect("register ok");
+        let mut env = make("OutOfSpace-v0", KwArgs::new()).expect("make ok");
+        assert!(matches!(env.try_reset(None), Err(GymError::InvalidObservation(_))));
+        // Only the first reset is checked.
+        assert!(env.try_reset(None).is_ok());
+        // The plain API keeps running, as Gymnasium's passive checker only warns.
+        let mut env = make("OutOfSpace-v0", KwArgs::new()).expect("make ok");
+        assert_eq!(env.reset(None).0, DynValue::Int(7));
+        assert_eq!(env.step(DynValue::Int(1)).observation, DynValue::Int(7));
+        // ...and records what it found where callers of make() can see it.
+        let warnings = env.env_checker_warnings();
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(warnings.iter().all(|w| matches!(w, GymError::InvalidObservation(_))));
+
+        let mut spec = EnvSpec::new("OutOfSpaceUnchecked-v0");
+        spec.order_enforce = false;
+        spec.disable_env_checker = true;
+        register(spec, factory_of(|_k| OutOfSpace)).expect("register ok");
+        let mut env = make("OutOfSpaceUnchecked-v0", KwArgs::new()).expect("make ok");
+        assert!(env.try_step(DynValue::Int(1)).is_ok());
+        assert!(env.try_reset(None).is_ok());
+        assert!(env.env_checker_warnings().is_empty());
+    }
+
+    #[test]
+    fn random_agent_drives_every_builtin_env() {
+        use crate::spaces::Space;
+        use crate::utils::rng_from_seed;
+        let mut rng = rng_from_seed(0);
+        for id in ["CartPole-v1", "MountainCar-v0", "MountainCarContinuous-v0", "Acrobot-v1", "Pendulum-v1", "LunarLander-v3"] {
+            let mut env = make(id, KwArgs::new()).expect("make ok");
+            let (obs_space, act_space) = (env.observation_space(), env.action_space());
+            let (mut obs, _) = env.reset(Some(0));
+            for _ in 0..20 {
+                assert!(obs_space.contains(&obs), "{id}: {obs:?}");
+                let s = env.try_step(act_space.sample(&mut rng)).expect("sampled action is valid");
+                obs = s.observation;
+                if s.terminated || s.truncated {
+                    obs = env.reset(None).0;
+                }
+            }
+        }
+    }
 }
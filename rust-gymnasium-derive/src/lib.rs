@@ -0,0 +1,359 @@
+//! `#[derive(Observation)]` for rust-gymnasium (enable its "derive" feature).
+//!
+//! Deriving `Observation` on a struct with named fields generates a `<Name>Space` struct holding
+//! one space per field, implementing `Space<Element = Name>`, `Flatten` (fields concatenated
+//! in declaration order) and `DynConvert` (a `DynSpace::Dict` keyed by field name, so envs can be
+//! registered and built with `make`), and implements `Observation` and `CheckFinite` (for
+//! `check_env`) for the struct. The space of each field is chosen from its type and `#[space(...)]` attribute:
+//!
+//! | field type  | space               | attributes                                            |
+//! |-------------|---------------------|-------------------------------------------------------|
+//! | `[T; N]`    | `BoxSpace<T, N>`    | `low`, `high`: arrays or scalars (default: unbounded) |
+//! | `Vec<T>`    | `ShapedBoxSpace`    | `shape` (required), `low`, `high`: scalars            |
+//! | `u32`       | `Discrete`          | `n` (required), `start`                               |
+//! | `Vec<u32>`  | `MultiDiscrete`     | `nvec` (required), `start`                            |
+//! | `Vec<u8>`   | `MultiBinary`       | `n` (required)                                        |
+//! | `BitVector` | `PackedMultiBinary` | `n` (required)                                        |
+//! | `String`    | `TextSpace`         | `max_length` (required), `min_length`, `charset`      |
+//! | other types | `T::Space`          | none; `T` must implement `Observation`                |
+//!
+//! "Unbounded" means infinite bounds for floats and the type's MIN/MAX for integers.
+//!
+//! Container attributes, in `#[observation(...)]`: `space = Name` renames the generated space
+//! struct, and `no_flatten`/`no_dyn` skip the `Flatten`/`DynConvert` impls (for fields whose
+//! spaces have none).
+//!
+//! ```ignore
+//! #[derive(Clone, Debug, PartialEq, Observation)]
+//! struct Obs {
+//!     #[space(low = -1.0, high = 1.0)]
+//!     pos: [f32; 2],
+//!     #[space(n = 3)]
+//!     mode: u32,
+//! }
+//! let space: ObsSpace = Obs::space();
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Expr, Fields, GenericArgument, Ident, PathArguments, Type};
+
+#[proc_macro_derive(Observation, attributes(space, observation))]
+pub fn derive_observation(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// `key = value` pairs of a field's `#[space(...)]` attributes.
+struct FieldAttrs {
+    span: Span,
+    entries: Vec<(Ident, Expr)>,
+}
+
+impl FieldAttrs {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut entries = Vec::new();
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("space")) {
+            attr.parse_nested_meta(|meta| {
+                let key = meta.path.require_ident()?.clone();
+                if entries.iter().any(|(k, _)| *k == key) {
+                    return Err(meta.error(format!("duplicate space attribute `{key}`")));
+                }
+                entries.push((key, meta.value()?.parse()?));
+                Ok(())
+            })?;
+        }
+        Ok(Self { span: field.span(), entries })
+    }
+
+    fn get(&self, key: &str) -> Option<&Expr> { self.entries.iter().find(|(k, _)| k == key).map(|(_, v)| v) }
+
+    fn require(&self, key: &str, kind: &str) -> syn::Result<&Expr> {
+        self.get(key)
+            .ok_or_else(|| syn::Error::new(self.span, format!("{kind} fields need #[space({key} = ...)]")))
+    }
+
+    /// Reject attributes that do not apply to a field of the given kind.
+    fn allow(&self, allowed: &[&str], kind: &str) -> syn::Result<()> {
+        match self.entries.iter().find(|(k, _)| !allowed.iter().any(|a| k == a)) {
+            Some((key, _)) => Err(syn::Error::new(key.span(), format!("`{key}` does not apply to {kind} fields"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Space type and constructor of one field.
+struct FieldSpace {
+    ty: TokenStream2,
+    init: TokenStream2,
+}
+
+fn is_float(ty: &Type) -> bool { matches!(ident_of(ty).as_deref(), Some("f32" | "f64")) }
+
+fn is_numeric(ty: &Type) -> bool {
+    matches!(
+        ident_of(ty).as_deref(),
+        Some("f32" | "f64" | "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize")
+    )
+}
+
+/// The identifier of a single-segment path type, e.g. "u32".
+fn ident_of(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.get_ident().map(Ident::to_string),
+        _ => None,
+    }
+}
+
+/// `T` of a `Vec<T>` type.
+fn vec_item(ty: &Type) -> Option<&Type> {
+    let Type::Path(p) = ty else { return None };
+    let last = p.path.segments.last()?;
+    if last.ident != "Vec" {
+        return None;
+    }
+    match &last.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(t) => Some(t),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Default Box bounds of element type `t`.
+fn unbounded(t: &Type) -> (TokenStream2, TokenStream2) {
+    if is_float(t) {
+        (quote!(<#t>::NEG_INFINITY), quote!(<#t>::INFINITY))
+    } else {
+        (quote!(<#t>::MIN), quote!(<#t>::MAX))
+    }
+}
+
+fn field_space(ty: &Type, attrs: &FieldAttrs) -> syn::Result<FieldSpace> {
+    let gym = quote!(::rust_gymnasium::spaces);
+    if let Type::Array(array) = ty {
+        let (t, len) = (&*array.elem, &array.len);
+        if !is_numeric(t) {
+            return Err(syn::Error::new(t.span(), "array fields need a numeric element type"));
+        }
+        attrs.allow(&["low", "high"], "array")?;
+        let (default_low, default_high) = unbounded(t);
+        // Array literals give per-dimension bounds; anything else is broadcast.
+        let bound = |key: &str, default: TokenStream2| match attrs.get(key) {
+            Some(e @ (Expr::Array(_) | Expr::Repeat(_))) => quote!(#e),
+            Some(e) => quote!([#e; #len]),
+            None => quote!([#default; #len]),
+        };
+        let (low, high) = (bound("low", default_low), bound("high", default_high));
+        return Ok(FieldSpace { ty: quote!(#gym::BoxSpace<#t, #len>), init: quote!(#gym::BoxSpace::new(#low, #high)) });
+    }
+    if let Some(t) = vec_item(ty) {
+        if attrs.get("nvec").is_some() {
+            attrs.allow(&["nvec", "start"], "MultiDiscrete")?;
+            let nvec = attrs.require("nvec", "MultiDiscrete")?;
+            let init = match attrs.get("start") {
+                Some(start) => quote!(#gym::MultiDiscrete::with_start(#nvec, #start)),
+                None => quote!(#gym::MultiDiscrete::new(#nvec)),
+            };
+            return Ok(FieldSpace { ty: quote!(#gym::MultiDiscrete), init });
+        }
+        if attrs.get("shape").is_none() && ident_of(t).as_deref() == Some("u8") {
+            attrs.allow(&["n"], "MultiBinary")?;
+            let n = attrs.require("n", "MultiBinary (Vec<u8>)")?;
+            return Ok(FieldSpace { ty: quote!(#gym::MultiBinary), init: quote!(#gym::MultiBinary::new(#n)) });
+        }
+        if !is_numeric(t) {
+            return Err(syn::Error::new(t.span(), "Vec fields need a numeric element type"));
+        }
+        attrs.allow(&["shape", "low", "high"], "Vec")?;
+        let shape = attrs.require("shape", "Vec")?;
+        let (default_low, default_high) = unbounded(t);
+        let low = attrs.get("low").map_or(default_low, |e| quote!(#e));
+        let high = attrs.get("high").map_or(default_high, |e| quote!(#e));
+        return Ok(FieldSpace {
+            ty: quote!(#gym::ShapedBoxSpace<#t>),
+            init: quote!(#gym::ShapedBoxSpace::uniform(#shape, #low, #high)),
+        });
+    }
+    match ident_of(ty).as_deref() {
+        Some("u32") => {
+            attrs.allow(&["n", "start"], "u32")?;
+            let n = attrs.require("n", "u32")?;
+            let start = attrs.get("start").map_or(quote!(0), |e| quote!(#e));
+            Ok(FieldSpace { ty: quote!(#gym::Discrete), init: quote!(#gym::Discrete::with_start(#n, #start)) })
+        }
+        Some("BitVector") => {
+            attrs.allow(&["n"], "BitVector")?;
+            let n = attrs.require("n", "BitVector")?;
+            Ok(FieldSpace { ty: quote!(#gym::PackedMultiBinary), init: quote!(#gym::PackedMultiBinary::new(#n)) })
+        }
+        Some("String") => {
+            attrs.allow(&["min_length", "max_length", "charset"], "String")?;
+            let max = attrs.require("max_length", "String")?;
+            let min = attrs.get("min_length").map_or(quote!(0), |e| quote!(#e));
+            let init = match attrs.get("charset") {
+                Some(charset) => quote!(#gym::TextSpace::with_charset(#min, #max, ::std::primitive::str::chars(#charset))),
+                None => quote!(#gym::TextSpace::new(#min, #max)),
+            };
+            Ok(FieldSpace { ty: quote!(#gym::TextSpace), init })
+        }
+        Some(other) if is_numeric(ty) => {
+            Err(syn::Error::new(ty.span(), format!("scalar {other} fields have no space; use [{other}; 1] or u32")))
+        }
+        _ => {
+            attrs.allow(&[], "nested Observation")?;
+            Ok(FieldSpace {
+                ty: quote!(<#ty as #gym::Observation>::Space),
+                init: quote!(<#ty as #gym::Observation>::space()),
+            })
+        }
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(input.generics.span(), "#[derive(Observation)] does not support generic structs"));
+    }
+    let fields = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Named(named) => &named.named,
+            _ => return Err(syn::Error::new(name.span(), "#[derive(Observation)] needs a struct with named fields")),
+        },
+        _ => return Err(syn::Error::new(name.span(), "#[derive(Observation)] only supports structs")),
+    };
+
+    let mut space_name = format_ident!("{}Space", name);
+    let mut flatten = true;
+    let mut dyn_convert = true;
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("observation")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("space") {
+                space_name = meta.value()?.parse()?;
+            } else if meta.path.is_ident("no_flatten") {
+                flatten = false;
+            } else if meta.path.is_ident("no_dyn") {
+                dyn_convert = false;
+            } else {
+                return Err(meta.error("expected `space = Name`, `no_flatten` or `no_dyn`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    let idents: Vec<&Ident> = fields.iter().map(|f| f.ident.as_ref().expect("named field")).collect();
+    let vis: Vec<&syn::Visibility> = fields.iter().map(|f| &f.vis).collect();
+    let spaces = fields
+        .iter()
+        .map(|f| field_space(&f.ty, &FieldAttrs::parse(f)?))
+        .collect::<syn::Result<Vec<FieldSpace>>>()?;
+    let space_tys: Vec<&TokenStream2> = spaces.iter().map(|s| &s.ty).collect();
+    let inits: Vec<&TokenStream2> = spaces.iter().map(|s| &s.init).collect();
+    let labels: Vec<String> = idents.iter().map(|i| format!("{name}.{i}")).collect();
+    let struct_vis = &input.vis;
+    let gym = quote!(::rust_gymnasium);
+    let doc = format!("Space of `{name}` observations, generated by `#[derive(Observation)]`.");
+
+    let flatten_impl = flatten.then(|| {
+        let label = name.to_string();
+        quote! {
+            impl #gym::spaces::Flatten for #space_name {
+                fn flatdim(&self) -> usize { 0 #(+ #gym::spaces::Flatten::flatdim(&self.#idents))* }
+
+                fn flatten(&self, elem: &#name) -> ::std::vec::Vec<f32> {
+                    let mut out = ::std::vec::Vec::with_capacity(#gym::spaces::Flatten::flatdim(self));
+                    #(out.extend(#gym::spaces::Flatten::flatten(&self.#idents, &elem.#idents));)*
+                    out
+                }
+
+                fn unflatten(&self, flat: &[f32]) -> #gym::Result<#name> {
+                    let dims = [#(#gym::spaces::Flatten::flatdim(&self.#idents)),*];
+                    let mut parts = #gym::__private::split_flat(#label, flat, &dims)?.into_iter();
+                    Ok(#name { #(#idents: #gym::spaces::Flatten::unflatten(&self.#idents, parts.next().expect("one part per field"))?,)* })
+                }
+
+                fn flatten_space(&self) -> #gym::spaces::ShapedBoxSpace<f32> {
+                    #gym::__private::concat_spaces(&[#(#gym::spaces::Flatten::flatten_space(&self.#idents)),*])
+                }
+            }
+        }
+    });
+
+    let dyn_impl = dyn_convert.then(|| {
+        let label = name.to_string();
+        let keys: Vec<String> = idents.iter().map(ToString::to_string).collect();
+        quote! {
+            impl #gym::spaces::DynConvert for #space_name {
+                fn to_dyn_space(&self) -> #gym::DynSpace {
+                    #gym::DynSpace::Dict(#gym::DictSpace::new([
+                        #((#keys, #gym::spaces::DynConvert::to_dyn_space(&self.#idents)),)*
+                    ]))
+                }
+
+                fn to_dyn_value(&self, elem: #name) -> #gym::Result<#gym::DynValue> {
+                    let mut fields = ::std::collections::BTreeMap::new();
+                    #(fields.insert(
+                        ::std::string::String::from(#keys),
+                        #gym::spaces::DynConvert::to_dyn_value(&self.#idents, elem.#idents)
+                            .map_err(|e| #gym::__private::nested(#labels, e))?,
+                    );)*
+                    Ok(#gym::DynValue::Dict(fields))
+                }
+
+                fn element_from_dyn(&self, value: #gym::DynValue) -> #gym::Result<#name> {
+                    let mut fields = #gym::__private::dyn_fields(#label, value, &[#(#keys),*])?;
+                    Ok(#name { #(#idents: #gym::spaces::DynConvert::element_from_dyn(
+                        &self.#idents,
+                        fields.remove(#keys).expect("checked keys"),
+                    ).map_err(|e| #gym::__private::nested(#labels, e))?,)* })
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Clone, Debug, PartialEq)]
+        #struct_vis struct #space_name {
+            #(#vis #idents: #space_tys,)*
+        }
+
+        impl #gym::spaces::Space for #space_name {
+            type Element = #name;
+
+            fn sample<R: #gym::__private::Rng + ?Sized>(&self, rng: &mut R) -> #name {
+                #name { #(#idents: #gym::spaces::Space::sample(&self.#idents, rng),)* }
+            }
+
+            fn contains(&self, elem: &#name) -> bool {
+                true #(&& #gym::spaces::Space::contains(&self.#idents, &elem.#idents))*
+            }
+
+            fn validate(&self, elem: &#name) -> #gym::Result<()> {
+                #(#gym::spaces::Space::validate(&self.#idents, &elem.#idents)
+                    .map_err(|e| #gym::__private::nested(#labels, e))?;)*
+                Ok(())
+            }
+        }
+
+        impl #gym::utils::env_checker::CheckFinite for #name {
+            fn all_finite(&self) -> bool {
+                true #(&& #gym::utils::env_checker::CheckFinite::all_finite(&self.#idents))*
+            }
+        }
+
+        impl #gym::spaces::Observation for #name {
+            type Space = #space_name;
+
+            fn space() -> #space_name {
+                #space_name { #(#idents: #inits,)* }
+            }
+        }
+
+        #flatten_impl
+        #dyn_impl
+    })
+}
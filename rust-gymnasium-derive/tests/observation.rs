@@ -0,0 +1,165 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::collections::BTreeMap;
+
+use rust_gymnasium::spaces::{
+    flatdim, flatten, flatten_space, unflatten, BitVector, DynConvert, Flatten, MultiDiscrete, TextSpace,
+};
+use rust_gymnasium::{
+    factory_of, make, register, BoxSpace, Discrete, DynSpace, DynValue, Env, EnvSpec, GymError, Info, KwArgs,
+    Observation, Space, Step,
+};
+
+#[derive(Clone, Debug, PartialEq, Observation)]
+struct Obs {
+    #[space(low = -1.0, high = [1.0, 2.0])]
+    pos: [f32; 2],
+    #[space(n = 3, start = 1)]
+    mode: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Observation)]
+#[observation(space = SensorSpace)]
+struct Sensors {
+    body: Obs,
+    #[space(shape = [2, 2], low = 0, high = 9)]
+    grid: Vec<u8>,
+    #[space(nvec = vec![2, 3])]
+    buttons: Vec<u32>,
+    #[space(n = 4)]
+    contacts: Vec<u8>,
+    #[space(n = 100)]
+    visited: BitVector,
+    #[space(max_length = 3, charset = "ab")]
+    label: String,
+    speed: [f64; 1],
+}
+
+#[test]
+fn derived_space_matches_field_attributes() {
+    let space = Obs::space();
+    assert_eq!(space.pos, BoxSpace::new([-1.0, -1.0], [1.0, 2.0]));
+    assert_eq!(space.mode, Discrete::with_start(3, 1));
+
+    let sensors = Sensors::space();
+    assert_eq!(sensors.body, space);
+    assert_eq!(sensors.grid.shape(), &[2, 2]);
+    assert_eq!(sensors.buttons, MultiDiscrete::new([2, 3]));
+    assert_eq!(sensors.contacts.n(), 4);
+    assert_eq!(sensors.visited.n(), 100);
+    assert_eq!(sensors.label, TextSpace::with_charset(0, 3, "ab".chars()));
+    assert_eq!(sensors.speed.high(), &[f64::INFINITY]);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..20 {
+        let obs = sensors.sample(&mut rng);
+        assert!(sensors.contains(&obs));
+        assert!(sensors.validate(&obs).is_ok());
+    }
+}
+
+#[test]
+fn derived_space_validates_fields() {
+    let space: SensorSpace = Sensors::space();
+    let mut obs = space.sample(&mut StdRng::seed_from_u64(1));
+    obs.body.mode = 0;
+    assert!(!space.contains(&obs));
+    let err = space.validate(&obs).unwrap_err();
+    assert_eq!(err.to_string(), "Sensors.body: Obs.mode: Discrete: 0 is outside [1, 4)");
+    assert!(matches!(err, GymError::OutOfSpace(_)));
+}
+
+#[test]
+fn derived_space_flattens_fields_in_order() {
+    let space = Obs::space();
+    assert_eq!(flatdim(&space), 5);
+    let obs = Obs { pos: [0.5, -0.25], mode: 2 };
+    assert_eq!(flatten(&space, &obs), vec![0.5, -0.25, 0.0, 1.0, 0.0]);
+    assert_eq!(unflatten(&space, &[0.5, -0.25, 0.0, 1.0, 0.0]).unwrap(), obs);
+    assert!(matches!(unflatten(&space, &[0.0; 4]), Err(GymError::InvalidObservation(_))));
+    let flat = flatten_space(&space);
+    assert_eq!(flat.low(), &[-1.0, -1.0, 0.0, 0.0, 0.0]);
+    assert_eq!(flat.high(), &[1.0, 2.0, 1.0, 1.0, 1.0]);
+
+    let sensors = Sensors::space();
+    let mut elem = sensors.sample(&mut StdRng::seed_from_u64(2));
+    // Flattening goes through f32
+    elem.speed = [0.5];
+    assert_eq!(sensors.flatdim(), 5 + 4 + 5 + 4 + 100 + 3 + 1);
+    assert_eq!(sensors.unflatten(&sensors.flatten(&elem)).unwrap(), elem);
+}
+
+/// Envs can use the derived types directly.
+struct Walker {
+    obs: Obs,
+}
+
+impl Env for Walker {
+    type Obs = Obs;
+    type Act = u32;
+    type ObsSpace = ObsSpace;
+    type ActSpace = Discrete;
+    type Reward = f32;
+
+    fn observation_space(&self) -> ObsSpace { Obs::space() }
+    fn action_space(&self) -> Discrete { Discrete::with_start(3, 1) }
+
+    fn reset(&mut self, _seed: Option<u64>) -> (Obs, Info) {
+        self.obs = Obs { pos: [0.0, 0.0], mode: 1 };
+        (self.obs.clone(), Info::new())
+    }
+
+    fn step(&mut self, action: u32) -> Step<Obs> {
+        self.obs.mode = action;
+        self.obs.pos[0] = (self.obs.pos[0] + 0.1).min(1.0);
+        Step::new(self.obs.clone(), 0.0, false, false, Info::new())
+    }
+}
+
+#[test]
+fn derived_observations_pass_the_env_checker() {
+    let mut env = Walker { obs: Obs { pos: [0.0, 0.0], mode: 1 } };
+    let report = rust_gymnasium::check_env(&mut env);
+    assert!(report.is_ok(), "{:?}", report.failures);
+}
+
+#[test]
+fn derived_spaces_convert_to_dyn_dicts() {
+    let space = Obs::space();
+    let DynSpace::Dict(dict) = space.to_dyn_space() else { panic!("expected a Dict space") };
+    assert_eq!(dict.keys().collect::<Vec<_>>(), ["mode", "pos"]);
+
+    let obs = Obs { pos: [0.5, -0.25], mode: 2 };
+    let value = space.to_dyn_value(obs.clone()).unwrap();
+    let fields = BTreeMap::from([
+        ("mode".to_string(), DynValue::Int(2)),
+        ("pos".to_string(), DynValue::Floats(vec![0.5, -0.25])),
+    ]);
+    assert_eq!(value, DynValue::Dict(fields));
+    assert!(space.to_dyn_space().contains(&value));
+    assert_eq!(space.element_from_dyn(value).unwrap(), obs);
+
+    let missing = DynValue::Dict(BTreeMap::from([("mode".to_string(), DynValue::Int(2))]));
+    assert_eq!(space.element_from_dyn(missing).unwrap_err().to_string(), "Obs: missing field \"pos\"");
+
+    let sensors = Sensors::space();
+    let elem = sensors.sample(&mut StdRng::seed_from_u64(3));
+    let value = sensors.to_dyn_value(elem.clone()).unwrap();
+    assert!(sensors.to_dyn_space().contains(&value));
+    assert_eq!(sensors.element_from_dyn(value).unwrap(), elem);
+}
+
+#[test]
+fn derived_observation_envs_run_through_make() {
+    register(EnvSpec::new("DerivedWalker-v0"), factory_of(|_k| Walker { obs: Obs { pos: [0.0, 0.0], mode: 1 } }))
+        .expect("register ok");
+    let mut env = make("DerivedWalker-v0", KwArgs::new()).expect("make ok");
+    let obs_space = env.observation_space();
+    let (obs, _) = env.try_reset(Some(0)).expect("reset ok");
+    assert!(obs_space.contains(&obs));
+    let s = env.try_step(DynValue::Int(3)).expect("step ok");
+    let DynValue::Dict(fields) = &s.observation else { panic!("expected a Dict value") };
+    assert_eq!(fields["mode"], DynValue::Int(3));
+    assert!(obs_space.contains(&s.observation));
+    assert!(matches!(env.try_step(DynValue::Int(0)), Err(GymError::InvalidAction(_))));
+}
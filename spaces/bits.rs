@@ -0,0 +1,216 @@
+// Packed bit vectors and the MultiBinary space over them, for large binary states where one
+// `u8` per bit is too costly.
+
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use rand::Rng;
+
+use super::{check_len, MultiBinary, Space};
+use crate::core::{GymError, Result};
+
+const WORD_BITS: usize = 64;
+
+fn words_for(len: usize) -> usize { len.div_ceil(WORD_BITS) }
+
+/// The valid bits of the last word of a `len`-bit vector.
+fn tail_mask(len: usize) -> u64 {
+    match len % WORD_BITS {
+        0 => !0,
+        r => (1 << r) - 1,
+    }
+}
+
+/// A fixed-length vector of bits packed into `u64` words, bit `i` at bit `i % 64` of word `i / 64`.
+///
+/// Bits past `len` in the last word are always zero, so equality, hashing and `count_ones` work
+/// word by word. Bitwise operators panic when the lengths differ.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVector {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVector {
+    /// `len` zero bits.
+    pub fn zeros(len: usize) -> Self { Self { words: vec![0; words_for(len)], len } }
+
+    /// `len` one bits.
+    pub fn ones(len: usize) -> Self { !Self::zeros(len) }
+
+    /// Bits from packed words (see the layout above); bits past `len` are cleared.
+    /// Panics unless there are exactly `ceil(len / 64)` words.
+    pub fn from_words(mut words: Vec<u64>, len: usize) -> Self {
+        assert_eq!(words.len(), words_for(len), "{len} bits need {} words", words_for(len));
+        if let Some(last) = words.last_mut() { *last &= tail_mask(len); }
+        Self { words, len }
+    }
+
+    pub fn len(&self) -> usize { self.len }
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+    pub fn words(&self) -> &[u64] { &self.words }
+
+    /// Bit `i`, or `None` if out of range.
+    pub fn get(&self, i: usize) -> Option<bool> {
+        (i < self.len).then(|| self.words[i / WORD_BITS] >> (i % WORD_BITS) & 1 == 1)
+    }
+
+    /// Set bit `i`. Panics if `i` is out of range.
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < self.len, "bit {i} is out of range for {} bits", self.len);
+        let bit = 1 << (i % WORD_BITS);
+        if value { self.words[i / WORD_BITS] |= bit } else { self.words[i / WORD_BITS] &= !bit }
+    }
+
+    /// Number of one bits.
+    pub fn count_ones(&self) -> usize { self.words.iter().map(|w| w.count_ones() as usize).sum() }
+
+    /// Whether any bit is set.
+    pub fn any(&self) -> bool { self.words.iter().any(|&w| w != 0) }
+
+    /// The bits in order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ { (0..self.len).map(|i| self.get(i).expect("in range")) }
+
+    /// Indices of the one bits, in increasing order.
+    pub fn ones_indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(w, &word)| {
+            let mut rest = word;
+            std::iter::from_fn(move || {
+                (rest != 0).then(|| {
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    w * WORD_BITS + bit
+                })
+            })
+        })
+    }
+
+    /// The unpacked 0/1 form used by `MultiBinary`.
+    pub fn to_bytes(&self) -> Vec<u8> { self.iter().map(u8::from).collect() }
+
+    fn check_same_len(&self, other: &Self) {
+        assert_eq!(self.len, other.len, "bitwise operation on {} and {} bits", self.len, other.len);
+    }
+}
+
+impl fmt::Debug for BitVector {
+    /// The bits as a string of 0s and 1s, bit 0 first.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits: String = self.iter().map(|b| if b { '1' } else { '0' }).collect();
+        write!(f, "BitVector({bits})")
+    }
+}
+
+impl FromIterator<bool> for BitVector {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut words = Vec::new();
+        let mut len = 0;
+        for bit in iter {
+            if len % WORD_BITS == 0 { words.push(0); }
+            if bit { *words.last_mut().expect("pushed") |= 1 << (len % WORD_BITS); }
+            len += 1;
+        }
+        Self { words, len }
+    }
+}
+
+impl TryFrom<&[u8]> for BitVector {
+    type Error = GymError;
+
+    /// Pack the 0/1 form used by `MultiBinary`; other values are `GymError::OutOfSpace`.
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        match bytes.iter().position(|&v| v > 1) {
+            Some(i) => Err(GymError::OutOfSpace(format!("bit {i}: {} is not 0 or 1", bytes[i]))),
+            None => Ok(bytes.iter().map(|&v| v == 1).collect()),
+        }
+    }
+}
+
+impl From<&BitVector> for Vec<u8> {
+    fn from(bits: &BitVector) -> Self { bits.to_bytes() }
+}
+
+impl From<BitVector> for Vec<u8> {
+    fn from(bits: BitVector) -> Self { bits.to_bytes() }
+}
+
+macro_rules! bitwise {
+    ($($Op:ident $op:ident $OpAssign:ident $op_assign:ident),*) => {$(
+        impl $OpAssign<&BitVector> for BitVector {
+            fn $op_assign(&mut self, rhs: &BitVector) {
+                self.check_same_len(rhs);
+                for (a, b) in self.words.iter_mut().zip(&rhs.words) {
+                    a.$op_assign(b);
+                }
+            }
+        }
+
+        impl $Op<&BitVector> for BitVector {
+            type Output = BitVector;
+            fn $op(mut self, rhs: &BitVector) -> BitVector {
+                self.$op_assign(rhs);
+                self
+            }
+        }
+
+        impl $Op<&BitVector> for &BitVector {
+            type Output = BitVector;
+            fn $op(self, rhs: &BitVector) -> BitVector { self.clone().$op(rhs) }
+        }
+    )*};
+}
+
+bitwise!(BitAnd bitand BitAndAssign bitand_assign, BitOr bitor BitOrAssign bitor_assign, BitXor bitxor BitXorAssign bitxor_assign);
+
+impl Not for BitVector {
+    type Output = BitVector;
+    fn not(mut self) -> BitVector {
+        for w in &mut self.words {
+            *w = !*w;
+        }
+        if let Some(last) = self.words.last_mut() { *last &= tail_mask(self.len); }
+        self
+    }
+}
+
+impl Not for &BitVector {
+    type Output = BitVector;
+    fn not(self) -> BitVector { !self.clone() }
+}
+
+/// `MultiBinary` with packed `BitVector` elements: sampling draws whole `u64` words from the RNG
+/// and `contains` only checks the length.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackedMultiBinary {
+    n: usize,
+}
+
+impl PackedMultiBinary {
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "PackedMultiBinary requires n > 0");
+        Self { n }
+    }
+
+    pub fn n(&self) -> usize { self.n }
+
+    /// The same space with unpacked `Vec<u8>` elements.
+    pub fn unpacked(&self) -> MultiBinary { MultiBinary::new(self.n) }
+}
+
+impl MultiBinary {
+    /// The same space with packed `BitVector` elements.
+    pub fn packed(&self) -> PackedMultiBinary { PackedMultiBinary::new(self.n()) }
+}
+
+impl Space for PackedMultiBinary {
+    type Element = BitVector;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> BitVector {
+        // Uniform bits, 64 at a time
+        BitVector::from_words((0..words_for(self.n)).map(|_| rng.r#gen::<u64>()).collect(), self.n)
+    }
+
+    fn contains(&self, elem: &BitVector) -> bool { elem.len() == self.n }
+
+    fn validate(&self, elem: &BitVector) -> Result<()> { check_len("PackedMultiBinary", elem.len(), self.n) }
+}
@@ -0,0 +1,83 @@
+// Dict spaces: named subspaces (Gymnasium's `spaces.Dict`).
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use super::{nested, Space};
+use crate::core::{GymError, Result};
+
+/// A space of named subspaces, e.g. `observation`, `achieved_goal` and `desired_goal` for
+/// goal-conditioned envs. Elements are maps with exactly the same keys, each value belonging
+/// to the subspace of that key.
+///
+/// Keys are kept sorted, as Gymnasium does for plain dicts, so sampling order and iteration
+/// are deterministic. All subspaces share one type `S`; subspaces of different kinds need an
+/// enum implementing `Space`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DictSpace<S> {
+    spaces: BTreeMap<String, S>,
+}
+
+impl<S: Space> DictSpace<S> {
+    pub fn new<K: Into<String>, I: IntoIterator<Item = (K, S)>>(spaces: I) -> Self {
+        let spaces: BTreeMap<String, S> = spaces.into_iter().map(|(k, s)| (k.into(), s)).collect();
+        assert!(!spaces.is_empty(), "DictSpace requires at least one subspace");
+        Self { spaces }
+    }
+
+    /// The subspace stored under `key`.
+    pub fn get(&self, key: &str) -> Option<&S> { self.spaces.get(key) }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> { self.spaces.keys().map(String::as_str) }
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &S)> { self.spaces.iter().map(|(k, s)| (k.as_str(), s)) }
+    pub fn spaces(&self) -> &BTreeMap<String, S> { &self.spaces }
+    pub fn len(&self) -> usize { self.spaces.len() }
+    pub fn is_empty(&self) -> bool { self.spaces.is_empty() }
+
+    /// The sub-dict with only `keys`, like Gymnasium's `FilterObservation`.
+    /// Panics if a key is missing or no key is given.
+    pub fn filter<'a, I: IntoIterator<Item = &'a str>>(&self, keys: I) -> Self
+    where
+        S: Clone,
+    {
+        Self::new(keys.into_iter().map(|k| {
+            let space = self.spaces.get(k).unwrap_or_else(|| panic!("DictSpace has no key {k:?}"));
+            (k, space.clone())
+        }))
+    }
+
+    /// Restrict an element to the keys of this space, e.g. to map an element of the full space
+    /// into a space built with `filter`. Keys absent from `elem` are skipped.
+    pub fn filter_element(&self, elem: &BTreeMap<String, S::Element>) -> BTreeMap<String, S::Element>
+    where
+        S::Element: Clone,
+    {
+        self.spaces.keys().filter_map(|k| elem.get(k).map(|v| (k.clone(), v.clone()))).collect()
+    }
+}
+
+impl<S: Space> Space for DictSpace<S> {
+    type Element = BTreeMap<String, S::Element>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        // Subspaces are sampled in key order, so a seeded RNG yields a reproducible map.
+        self.spaces.iter().map(|(k, s)| (k.clone(), s.sample(rng))).collect()
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool {
+        elem.len() == self.spaces.len()
+            && self.spaces.iter().all(|(k, s)| elem.get(k).is_some_and(|v| s.contains(v)))
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        if let Some(k) = elem.keys().find(|k| !self.spaces.contains_key(*k)) {
+            return Err(GymError::OutOfSpace(format!("DictSpace: unexpected key {k:?}")));
+        }
+        for (k, s) in &self.spaces {
+            let v = elem.get(k).ok_or_else(|| GymError::OutOfSpace(format!("DictSpace: missing key {k:?}")))?;
+            s.validate(v).map_err(|e| nested(format_args!("DictSpace key {k:?}"), e))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,214 @@
+// Non-uniform sampling (Gymnasium's `probability` argument of `sample`, extended to Box spaces):
+// distributions bound to a space, usable with `rng.sample(&dist)`.
+
+use rand::distributions::{Bernoulli, Distribution, WeightedIndex};
+use rand::Rng;
+
+use super::{standard_exponential, standard_normal, BoxScalar, BoxSpace, Discrete, MultiBinary, MultiDiscrete, ShapedBoxSpace};
+
+/// Categorical distribution over the elements of a `Discrete` space; see `Discrete::categorical`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Categorical {
+    start: u32,
+    index: WeightedIndex<f64>,
+}
+
+impl Distribution<u32> for Categorical {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 { self.start + self.index.sample(rng) as u32 }
+}
+
+/// One independent `Categorical` per `MultiDiscrete` dimension; see `MultiDiscrete::categorical`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiCategorical {
+    dims: Vec<Categorical>,
+}
+
+impl Distribution<Vec<u32>> for MultiCategorical {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u32> { self.dims.iter().map(|d| d.sample(rng)).collect() }
+}
+
+/// Independent bits with per-bit probabilities of 1; see `MultiBinary::bernoulli`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiBernoulli {
+    bits: Vec<Bernoulli>,
+}
+
+impl Distribution<Vec<u8>> for MultiBernoulli {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u8> { self.bits.iter().map(|b| u8::from(b.sample(rng))).collect() }
+}
+
+impl Discrete {
+    /// Sample `start + i` with probability proportional to `weights[i]`.
+    ///
+    /// Panics unless there are `n` weights, all finite and non-negative, with a positive sum.
+    pub fn categorical<W: Into<Vec<f64>>>(&self, weights: W) -> Categorical {
+        let weights = weights.into();
+        assert_eq!(weights.len(), self.n() as usize, "Discrete({}) needs {} weights", self.n(), self.n());
+        let index = WeightedIndex::new(&weights)
+            .unwrap_or_else(|e| panic!("Discrete: invalid categorical weights {weights:?}: {e}"));
+        Categorical { start: self.start(), index }
+    }
+}
+
+impl MultiDiscrete {
+    /// One set of categorical weights per dimension (see `Discrete::categorical`).
+    pub fn categorical<I, W>(&self, weights: I) -> MultiCategorical
+    where
+        I: IntoIterator<Item = W>,
+        W: Into<Vec<f64>>,
+    {
+        let weights: Vec<W> = weights.into_iter().collect();
+        assert_eq!(weights.len(), self.ndim(), "MultiDiscrete needs one set of weights per dimension");
+        MultiCategorical { dims: weights.into_iter().enumerate().map(|(i, w)| self.dim(i).categorical(w)).collect() }
+    }
+}
+
+impl MultiBinary {
+    /// Bit `i` is 1 with probability `p[i]`. Panics unless there are `n` probabilities in [0, 1].
+    pub fn bernoulli<P: Into<Vec<f64>>>(&self, p: P) -> MultiBernoulli {
+        let p = p.into();
+        assert_eq!(p.len(), self.n(), "MultiBinary({}) needs {} probabilities", self.n(), self.n());
+        let bits = p
+            .iter()
+            .map(|&p| Bernoulli::new(p).unwrap_or_else(|_| panic!("MultiBinary: probability {p} is not in [0, 1]")))
+            .collect();
+        MultiBernoulli { bits }
+    }
+}
+
+/// Per-dimension distribution of a `BoxDistribution`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Shape {
+    /// N(mean, std^2), clipped to the bounds.
+    Gaussian { mean: f64, std: f64 },
+    /// Beta(alpha, beta) on [0, 1], scaled to the bounds.
+    Beta { alpha: f64, beta: f64 },
+}
+
+/// Per-dimension Gaussian or Beta distribution over a Box space; see `BoxSpace::gaussian` and
+/// `BoxSpace::beta` (and their `ShapedBoxSpace` counterparts).
+///
+/// Samples always lie within the bounds; integer dtypes round the continuous sample.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoxDistribution<S> {
+    space: S,
+    dims: Vec<Shape>,
+}
+
+impl<S> BoxDistribution<S> {
+    /// The space samples are drawn from.
+    pub fn space(&self) -> &S { &self.space }
+}
+
+fn gaussian_dims<T: BoxScalar>(mean: Vec<f64>, std: Vec<f64>, low: &[T]) -> Vec<Shape> {
+    assert_eq!(mean.len(), low.len(), "Box of {} dims needs {} means", low.len(), low.len());
+    assert_eq!(std.len(), low.len(), "Box of {} dims needs {} standard deviations", low.len(), low.len());
+    mean.into_iter()
+        .zip(std)
+        .map(|(mean, std)| {
+            assert!(mean.is_finite() && std.is_finite() && std >= 0.0, "Box: invalid Gaussian N({mean}, {std}^2)");
+            Shape::Gaussian { mean, std }
+        })
+        .collect()
+}
+
+fn beta_dims<T: BoxScalar>(alpha: Vec<f64>, beta: Vec<f64>, low: &[T], high: &[T]) -> Vec<Shape> {
+    assert_eq!(alpha.len(), low.len(), "Box of {} dims needs {} alphas", low.len(), low.len());
+    assert_eq!(beta.len(), low.len(), "Box of {} dims needs {} betas", low.len(), low.len());
+    assert!(low.iter().chain(high).all(|b| b.is_finite()), "Box: Beta sampling needs finite bounds");
+    alpha
+        .into_iter()
+        .zip(beta)
+        .map(|(alpha, beta)| {
+            assert!(alpha > 0.0 && beta > 0.0 && alpha.is_finite() && beta.is_finite(), "Box: invalid Beta({alpha}, {beta})");
+            Shape::Beta { alpha, beta }
+        })
+        .collect()
+}
+
+/// Gamma(shape, 1) by Marsaglia and Tsang's method, boosted by `u^(1 / shape)` for shape < 1.
+fn gamma<R: Rng + ?Sized>(shape: f64, rng: &mut R) -> f64 {
+    if shape < 1.0 {
+        return gamma(shape + 1.0, rng) * rng.r#gen::<f64>().powf(1.0 / shape);
+    }
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = standard_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v > 0.0 && -standard_exponential(rng) < 0.5 * x * x + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+fn sample_dims<T: BoxScalar, R: Rng + ?Sized>(dims: &[Shape], low: &[T], high: &[T], rng: &mut R) -> Vec<T> {
+    dims.iter()
+        .zip(low.iter().zip(high))
+        .map(|(shape, (&low, &high))| {
+            let (lo, hi) = (low.to_f64(), high.to_f64());
+            let v = match *shape {
+                Shape::Gaussian { mean, std } => mean + std * standard_normal(rng),
+                Shape::Beta { alpha, beta } => {
+                    let (x, y) = (gamma(alpha, rng), gamma(beta, rng));
+                    // With tiny parameters both draws can underflow to 0; Beta(alpha, beta) then
+                    // tends to its limit, 1 with probability alpha / (alpha + beta) and 0 otherwise.
+                    let unit = if x + y > 0.0 {
+                        x / (x + y)
+                    } else {
+                        f64::from(u8::from(rng.r#gen::<f64>() * (alpha + beta) < alpha))
+                    };
+                    lo + unit * (hi - lo)
+                }
+            };
+            // Clip in T as well, so rounding of integer dtypes cannot leave the bounds.
+            let v = T::from_f64(v.clamp(lo, hi));
+            if v < low { low } else if v > high { high } else { v }
+        })
+        .collect()
+}
+
+impl<T: BoxScalar, const N: usize> BoxSpace<T, N> {
+    /// Per-dimension N(mean, std^2), clipped to the bounds.
+    ///
+    /// Panics unless there are N means and N standard deviations, all finite, with std >= 0.
+    pub fn gaussian<M: Into<Vec<f64>>, D: Into<Vec<f64>>>(&self, mean: M, std: D) -> BoxDistribution<Self> {
+        let dims = gaussian_dims(mean.into(), std.into(), self.low());
+        BoxDistribution { space: self.clone(), dims }
+    }
+
+    /// Per-dimension Beta(alpha, beta), scaled from [0, 1] to the bounds.
+    ///
+    /// Panics unless the bounds are finite and there are N positive alphas and betas.
+    pub fn beta<A: Into<Vec<f64>>, B: Into<Vec<f64>>>(&self, alpha: A, beta: B) -> BoxDistribution<Self> {
+        let dims = beta_dims(alpha.into(), beta.into(), self.low(), self.high());
+        BoxDistribution { space: self.clone(), dims }
+    }
+}
+
+impl<T: BoxScalar, const N: usize> Distribution<[T; N]> for BoxDistribution<BoxSpace<T, N>> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [T; N] {
+        let values = sample_dims(&self.dims, self.space.low(), self.space.high(), rng);
+        values.try_into().expect("one value per dimension")
+    }
+}
+
+impl<T: BoxScalar> ShapedBoxSpace<T> {
+    /// Per-element N(mean, std^2), clipped to the bounds; see `BoxSpace::gaussian`.
+    pub fn gaussian<M: Into<Vec<f64>>, D: Into<Vec<f64>>>(&self, mean: M, std: D) -> BoxDistribution<Self> {
+        let dims = gaussian_dims(mean.into(), std.into(), self.low());
+        BoxDistribution { space: self.clone(), dims }
+    }
+
+    /// Per-element Beta(alpha, beta), scaled to the bounds; see `BoxSpace::beta`.
+    pub fn beta<A: Into<Vec<f64>>, B: Into<Vec<f64>>>(&self, alpha: A, beta: B) -> BoxDistribution<Self> {
+        let dims = beta_dims(alpha.into(), beta.into(), self.low(), self.high());
+        BoxDistribution { space: self.clone(), dims }
+    }
+}
+
+impl<T: BoxScalar> Distribution<Vec<T>> for BoxDistribution<ShapedBoxSpace<T>> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<T> {
+        sample_dims(&self.dims, self.space.low(), self.space.high(), rng)
+    }
+}
@@ -0,0 +1,420 @@
+// Untyped spaces and elements, so type-erased envs (`EnvDyn`) can be inspected and driven by
+// generic tooling without knowing their concrete types.
+
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use super::{
+    check_len, nested, BitVector, BoxScalar, BoxSpace, DictSpace, Discrete, GraphInstance, GraphSpace, MultiBinary,
+    MultiDiscrete, OneOf, PackedMultiBinary, SequenceSpace, ShapedBoxSpace, Space, TextSpace, TupleSpace,
+};
+use crate::core::{GymError, Result};
+
+/// An element of a `DynSpace`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DynValue {
+    /// Element of `Discrete`.
+    Int(i64),
+    /// Element of `MultiBinary`, `MultiDiscrete` or an integer Box (flat, row-major).
+    Ints(Vec<i64>),
+    /// Element of a float Box (flat, row-major).
+    Floats(Vec<f64>),
+    /// Element of `Text`.
+    Text(String),
+    /// Element of `Tuple` or `Sequence`.
+    List(Vec<DynValue>),
+    /// Element of `Dict`.
+    Dict(BTreeMap<String, DynValue>),
+    /// Element of `OneOf`: the variant index and its value.
+    OneOf(usize, Box<DynValue>),
+    /// Element of `Graph`.
+    Graph(Box<GraphInstance<DynValue, DynValue>>),
+}
+
+impl DynValue {
+    /// Name of the variant, for error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DynValue::Int(_) => "Int",
+            DynValue::Ints(_) => "Ints",
+            DynValue::Floats(_) => "Floats",
+            DynValue::Text(_) => "Text",
+            DynValue::List(_) => "List",
+            DynValue::Dict(_) => "Dict",
+            DynValue::OneOf(..) => "OneOf",
+            DynValue::Graph(_) => "Graph",
+        }
+    }
+}
+
+/// Any space, with untyped `DynValue` elements.
+///
+/// Box spaces are widened: float dtypes to `f64` bounds (`Box`), integer dtypes to `i64` bounds
+/// (`IntBox`). Tuples of any arity become a `Vec` of subspaces.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DynSpace {
+    Discrete(Discrete),
+    MultiBinary(MultiBinary),
+    MultiDiscrete(MultiDiscrete),
+    Box(ShapedBoxSpace<f64>),
+    IntBox(ShapedBoxSpace<i64>),
+    Text(TextSpace),
+    Tuple(Vec<DynSpace>),
+    Dict(DictSpace<DynSpace>),
+    Sequence(Box<SequenceSpace<DynSpace>>),
+    Graph(Box<GraphSpace<DynSpace, DynSpace>>),
+    OneOf(OneOf<DynSpace>),
+}
+
+impl DynSpace {
+    /// Gymnasium class name of the space, e.g. "Discrete" or "Box".
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DynSpace::Discrete(_) => "Discrete",
+            DynSpace::MultiBinary(_) => "MultiBinary",
+            DynSpace::MultiDiscrete(_) => "MultiDiscrete",
+            DynSpace::Box(_) | DynSpace::IntBox(_) => "Box",
+            DynSpace::Text(_) => "Text",
+            DynSpace::Tuple(_) => "Tuple",
+            DynSpace::Dict(_) => "Dict",
+            DynSpace::Sequence(_) => "Sequence",
+            DynSpace::Graph(_) => "Graph",
+            DynSpace::OneOf(_) => "OneOf",
+        }
+    }
+}
+
+/// Convert `DynValue` integers to a narrower type, naming the first value out of its range.
+fn narrow<T: TryFrom<i64>>(values: &[i64], what: &str) -> Result<Vec<T>> {
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| T::try_from(v).map_err(|_| GymError::OutOfSpace(format!("{what} dim {i}: {v} is out of range"))))
+        .collect()
+}
+
+impl Space for DynSpace {
+    type Element = DynValue;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        match self {
+            DynSpace::Discrete(s) => DynValue::Int(s.sample(rng).into()),
+            DynSpace::MultiBinary(s) => DynValue::Ints(s.sample(rng).into_iter().map(i64::from).collect()),
+            DynSpace::MultiDiscrete(s) => DynValue::Ints(s.sample(rng).into_iter().map(i64::from).collect()),
+            DynSpace::Box(s) => DynValue::Floats(s.sample(rng)),
+            DynSpace::IntBox(s) => DynValue::Ints(s.sample(rng)),
+            DynSpace::Text(s) => DynValue::Text(s.sample(rng)),
+            DynSpace::Tuple(spaces) => DynValue::List(spaces.iter().map(|s| s.sample(rng)).collect()),
+            DynSpace::Dict(s) => DynValue::Dict(s.sample(rng)),
+            DynSpace::Sequence(s) => DynValue::List(s.sample(rng)),
+            DynSpace::Graph(s) => DynValue::Graph(Box::new(s.sample(rng))),
+            DynSpace::OneOf(s) => {
+                let (index, value) = s.sample(rng);
+                DynValue::OneOf(index, Box::new(value))
+            }
+        }
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool { self.validate(elem).is_ok() }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        match (self, elem) {
+            (DynSpace::Discrete(s), DynValue::Int(v)) => {
+                let v = u32::try_from(*v).map_err(|_| {
+                    GymError::OutOfSpace(format!("Discrete: {v} is outside [{}, {})", s.start(), u64::from(s.start()) + u64::from(s.n())))
+                })?;
+                s.validate(&v)
+            }
+            (DynSpace::MultiBinary(s), DynValue::Ints(v)) => s.validate(&narrow(v, "MultiBinary")?),
+            (DynSpace::MultiDiscrete(s), DynValue::Ints(v)) => s.validate(&narrow(v, "MultiDiscrete")?),
+            (DynSpace::Box(s), DynValue::Floats(v)) => s.validate(v),
+            (DynSpace::IntBox(s), DynValue::Ints(v)) => s.validate(v),
+            (DynSpace::Text(s), DynValue::Text(v)) => s.validate(v),
+            (DynSpace::Tuple(spaces), DynValue::List(values)) => {
+                if values.len() != spaces.len() {
+                    return Err(GymError::OutOfSpace(format!(
+                        "Tuple: expected {} components, got {}",
+                        spaces.len(),
+                        values.len()
+                    )));
+                }
+                for (i, (s, v)) in spaces.iter().zip(values).enumerate() {
+                    s.validate(v).map_err(|e| nested(format_args!("tuple component {i}"), e))?;
+                }
+                Ok(())
+            }
+            (DynSpace::Dict(s), DynValue::Dict(v)) => s.validate(v),
+            (DynSpace::Sequence(s), DynValue::List(v)) => s.validate(v),
+            (DynSpace::Graph(s), DynValue::Graph(g)) => s.validate(g),
+            (DynSpace::OneOf(s), DynValue::OneOf(index, value)) => {
+                s.variant(*index)?.validate(value).map_err(|e| nested(format_args!("OneOf variant {index}"), e))
+            }
+            (space, value) => mismatch(space.kind(), value),
+        }
+    }
+}
+
+/// Typed spaces with an untyped counterpart, used to type-erase environments.
+///
+/// The value conversions only check what is needed to build the other representation (value
+/// kinds, lengths, keys, variant indices and integer ranges) and report mismatches as
+/// `GymError::OutOfSpace`; bounds are left to `Space::validate`.
+pub trait DynConvert: Space {
+    /// The untyped form of this space.
+    fn to_dyn_space(&self) -> DynSpace;
+
+    /// The untyped form of an element.
+    fn to_dyn_value(&self, elem: Self::Element) -> Result<DynValue>;
+
+    /// Rebuild a typed element from its untyped form.
+    fn element_from_dyn(&self, value: DynValue) -> Result<Self::Element>;
+}
+
+impl<S: DynConvert> From<S> for DynSpace {
+    fn from(space: S) -> Self { space.to_dyn_space() }
+}
+
+fn mismatch<T>(space: &str, value: &DynValue) -> Result<T> {
+    Err(GymError::OutOfSpace(format!("{space} space cannot hold a {} value", value.kind())))
+}
+
+fn is_float<T: BoxScalar>() -> bool { T::DTYPE.starts_with("float") }
+
+fn box_to_dyn<T: BoxScalar>(shape: &[usize], low: &[T], high: &[T]) -> DynSpace {
+    if is_float::<T>() {
+        let widen = |b: &[T]| b.iter().map(|&v| v.to_f64()).collect();
+        DynSpace::Box(ShapedBoxSpace::new(shape, widen(low), widen(high)))
+    } else {
+        let widen = |b: &[T]| b.iter().map(|&v| v.to_f64() as i64).collect();
+        DynSpace::IntBox(ShapedBoxSpace::new(shape, widen(low), widen(high)))
+    }
+}
+
+fn box_value<T: BoxScalar>(values: &[T]) -> DynValue {
+    if is_float::<T>() {
+        DynValue::Floats(values.iter().map(|&v| v.to_f64()).collect())
+    } else {
+        DynValue::Ints(values.iter().map(|&v| v.to_f64() as i64).collect())
+    }
+}
+
+/// Box values from the numeric `DynValue` the space widens to: `Floats` for float dtypes, `Ints`
+/// for integer dtypes, which must fit `T`.
+fn box_from_dyn<T: BoxScalar>(value: DynValue, len: usize) -> Result<Vec<T>> {
+    let values: Vec<T> = match value {
+        DynValue::Floats(v) if is_float::<T>() => v.into_iter().map(T::from_f64).collect(),
+        DynValue::Ints(v) if !is_float::<T>() => v
+            .iter()
+            .enumerate()
+            .map(|(i, &x)| T::from_i64(x).ok_or_else(|| GymError::OutOfSpace(format!("Box dim {i}: {x} is out of range"))))
+            .collect::<Result<_>>()?,
+        other => return mismatch("Box", &other),
+    };
+    check_len("Box", values.len(), len)?;
+    Ok(values)
+}
+
+impl DynConvert for Discrete {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::Discrete(self.clone()) }
+
+    fn to_dyn_value(&self, elem: u32) -> Result<DynValue> { Ok(DynValue::Int(elem.into())) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<u32> {
+        match value {
+            DynValue::Int(v) => u32::try_from(v).map_err(|_| GymError::OutOfSpace(format!("Discrete: {v} is out of range"))),
+            other => mismatch("Discrete", &other),
+        }
+    }
+}
+
+impl DynConvert for MultiBinary {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::MultiBinary(self.clone()) }
+
+    fn to_dyn_value(&self, elem: Vec<u8>) -> Result<DynValue> { Ok(DynValue::Ints(elem.into_iter().map(i64::from).collect())) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<Vec<u8>> {
+        match value {
+            DynValue::Ints(v) => narrow(&v, "MultiBinary"),
+            other => mismatch("MultiBinary", &other),
+        }
+    }
+}
+
+/// Unpacked to the `MultiBinary` form.
+impl DynConvert for PackedMultiBinary {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::MultiBinary(self.unpacked()) }
+
+    fn to_dyn_value(&self, elem: BitVector) -> Result<DynValue> { Ok(DynValue::Ints(elem.iter().map(i64::from).collect())) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<BitVector> {
+        BitVector::try_from(self.unpacked().element_from_dyn(value)?.as_slice())
+    }
+}
+
+impl DynConvert for MultiDiscrete {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::MultiDiscrete(self.clone()) }
+
+    fn to_dyn_value(&self, elem: Vec<u32>) -> Result<DynValue> { Ok(DynValue::Ints(elem.into_iter().map(i64::from).collect())) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<Vec<u32>> {
+        match value {
+            DynValue::Ints(v) => narrow(&v, "MultiDiscrete"),
+            other => mismatch("MultiDiscrete", &other),
+        }
+    }
+}
+
+impl<T: BoxScalar, const N: usize> DynConvert for BoxSpace<T, N> {
+    fn to_dyn_space(&self) -> DynSpace { box_to_dyn(&[N], self.low(), self.high()) }
+
+    fn to_dyn_value(&self, elem: [T; N]) -> Result<DynValue> { Ok(box_value(&elem)) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<[T; N]> {
+        let values = box_from_dyn(value, N)?;
+        Ok(std::array::from_fn(|i| values[i]))
+    }
+}
+
+impl<T: BoxScalar> DynConvert for ShapedBoxSpace<T> {
+    fn to_dyn_space(&self) -> DynSpace { box_to_dyn(self.shape(), self.low(), self.high()) }
+
+    fn to_dyn_value(&self, elem: Vec<T>) -> Result<DynValue> { Ok(box_value(&elem)) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<Vec<T>> { box_from_dyn(value, self.len()) }
+}
+
+impl DynConvert for TextSpace {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::Text(self.clone()) }
+
+    fn to_dyn_value(&self, elem: String) -> Result<DynValue> { Ok(DynValue::Text(elem)) }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<String> {
+        match value {
+            DynValue::Text(v) => Ok(v),
+            other => mismatch("Text", &other),
+        }
+    }
+}
+
+impl<S: DynConvert> DynConvert for TupleSpace<S> {
+    fn to_dyn_space(&self) -> DynSpace { self.spaces().to_dyn_space() }
+    fn to_dyn_value(&self, elem: S::Element) -> Result<DynValue> { self.spaces().to_dyn_value(elem) }
+    fn element_from_dyn(&self, value: DynValue) -> Result<S::Element> { self.spaces().element_from_dyn(value) }
+}
+
+macro_rules! dyn_tuple {
+    ($len:literal; $($S:ident $i:tt),+) => {
+        impl<$($S: DynConvert),+> DynConvert for ($($S,)+) {
+            fn to_dyn_space(&self) -> DynSpace { DynSpace::Tuple(vec![$(self.$i.to_dyn_space()),+]) }
+
+            fn to_dyn_value(&self, elem: Self::Element) -> Result<DynValue> {
+                Ok(DynValue::List(vec![$(self.$i.to_dyn_value(elem.$i)?),+]))
+            }
+
+            fn element_from_dyn(&self, value: DynValue) -> Result<Self::Element> {
+                let DynValue::List(values) = value else { return mismatch("Tuple", &value) };
+                check_len("Tuple", values.len(), $len)?;
+                let mut values = values.into_iter();
+                // The length was checked above, so every `next` yields a value.
+                Ok(($(self.$i.element_from_dyn(values.next().expect("checked length"))?,)+))
+            }
+        }
+    };
+}
+
+dyn_tuple!(1; A 0);
+dyn_tuple!(2; A 0, B 1);
+dyn_tuple!(3; A 0, B 1, C 2);
+dyn_tuple!(4; A 0, B 1, C 2, D 3);
+dyn_tuple!(5; A 0, B 1, C 2, D 3, E 4);
+dyn_tuple!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+dyn_tuple!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+dyn_tuple!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<S: DynConvert> DynConvert for DictSpace<S> {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::Dict(DictSpace::new(self.iter().map(|(k, s)| (k, s.to_dyn_space())))) }
+
+    fn to_dyn_value(&self, elem: BTreeMap<String, S::Element>) -> Result<DynValue> {
+        elem.into_iter()
+            .map(|(k, v)| match self.get(&k) {
+                Some(s) => Ok((k, s.to_dyn_value(v)?)),
+                None => Err(GymError::OutOfSpace(format!("DictSpace: unexpected key {k:?}"))),
+            })
+            .collect::<Result<_>>()
+            .map(DynValue::Dict)
+    }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<BTreeMap<String, S::Element>> {
+        let DynValue::Dict(values) = value else { return mismatch("Dict", &value) };
+        values
+            .into_iter()
+            .map(|(k, v)| match self.get(&k) {
+                Some(s) => Ok((k, s.element_from_dyn(v)?)),
+                None => Err(GymError::OutOfSpace(format!("DictSpace: unexpected key {k:?}"))),
+            })
+            .collect()
+    }
+}
+
+impl<S: DynConvert> DynConvert for SequenceSpace<S> {
+    fn to_dyn_space(&self) -> DynSpace {
+        let space = match self.max_length() {
+            Some(max) => SequenceSpace::bounded(self.space().to_dyn_space(), self.min_length(), max),
+            None => SequenceSpace::new(self.space().to_dyn_space()),
+        };
+        DynSpace::Sequence(Box::new(space))
+    }
+
+    fn to_dyn_value(&self, elem: Vec<S::Element>) -> Result<DynValue> {
+        elem.into_iter().map(|v| self.space().to_dyn_value(v)).collect::<Result<_>>().map(DynValue::List)
+    }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<Vec<S::Element>> {
+        let DynValue::List(values) = value else { return mismatch("Sequence", &value) };
+        values.into_iter().map(|v| self.space().element_from_dyn(v)).collect()
+    }
+}
+
+impl<N: DynConvert, E: DynConvert> DynConvert for GraphSpace<N, E> {
+    fn to_dyn_space(&self) -> DynSpace {
+        let space = GraphSpace::new(self.node_space().to_dyn_space(), self.edge_space().map(E::to_dyn_space));
+        DynSpace::Graph(Box::new(space.with_max_nodes(self.max_nodes())))
+    }
+
+    fn to_dyn_value(&self, elem: GraphInstance<N::Element, E::Element>) -> Result<DynValue> {
+        let nodes = elem.nodes.into_iter().map(|x| self.node_space().to_dyn_value(x)).collect::<Result<_>>()?;
+        let edges = match (self.edge_space(), elem.edges) {
+            (Some(space), Some(edges)) => Some(edges.into_iter().map(|e| space.to_dyn_value(e)).collect::<Result<_>>()?),
+            (None, None) => None,
+            _ => return Err(GymError::OutOfSpace("GraphSpace: edge features do not match the edge space".into())),
+        };
+        Ok(DynValue::Graph(Box::new(GraphInstance { nodes, edges, edge_links: elem.edge_links })))
+    }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<GraphInstance<N::Element, E::Element>> {
+        let DynValue::Graph(graph) = value else { return mismatch("Graph", &value) };
+        let GraphInstance { nodes, edges, edge_links } = *graph;
+        let nodes = nodes.into_iter().map(|x| self.node_space().element_from_dyn(x)).collect::<Result<_>>()?;
+        let edges = match (self.edge_space(), edges) {
+            (Some(space), Some(edges)) => Some(edges.into_iter().map(|e| space.element_from_dyn(e)).collect::<Result<_>>()?),
+            (None, None) => None,
+            _ => return Err(GymError::OutOfSpace("GraphSpace: edge features do not match the edge space".into())),
+        };
+        Ok(GraphInstance { nodes, edges, edge_links })
+    }
+}
+
+impl<S: DynConvert> DynConvert for OneOf<S> {
+    fn to_dyn_space(&self) -> DynSpace { DynSpace::OneOf(OneOf::new(self.spaces().iter().map(S::to_dyn_space))) }
+
+    fn to_dyn_value(&self, (index, value): (usize, S::Element)) -> Result<DynValue> {
+        Ok(DynValue::OneOf(index, Box::new(self.variant(index)?.to_dyn_value(value)?)))
+    }
+
+    fn element_from_dyn(&self, value: DynValue) -> Result<(usize, S::Element)> {
+        let DynValue::OneOf(index, value) = value else { return mismatch("OneOf", &value) };
+        Ok((index, self.variant(index)?.element_from_dyn(*value)?))
+    }
+}
@@ -0,0 +1,103 @@
+// Graph spaces: node features, optional edge features and edge links (Gymnasium's `spaces.Graph`).
+
+use rand::Rng;
+
+use super::{nested, Space};
+use crate::core::{GymError, Result};
+
+/// An element of a `GraphSpace`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GraphInstance<N, E> {
+    /// One feature element per node.
+    pub nodes: Vec<N>,
+    /// One feature element per edge, or `None` when the space has no edge features.
+    pub edges: Option<Vec<E>>,
+    /// `[from, to]` node indices of each edge.
+    pub edge_links: Vec<[usize; 2]>,
+}
+
+/// Graphs whose nodes carry elements of `node_space` and whose edges, if `edge_space` is set,
+/// carry elements of `edge_space`.
+///
+/// Sampling follows Gymnasium: a node count in [1, max_nodes] (default 10), an edge count in
+/// [0, n * (n - 1)] and uniformly random edge links.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSpace<N, E> {
+    node_space: N,
+    edge_space: Option<E>,
+    max_nodes: usize,
+}
+
+impl<N: Space, E: Space> GraphSpace<N, E> {
+    pub fn new(node_space: N, edge_space: Option<E>) -> Self { Self { node_space, edge_space, max_nodes: 10 } }
+
+    /// Largest number of nodes `sample` produces.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        assert!(max_nodes > 0, "GraphSpace requires max_nodes > 0");
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    pub fn node_space(&self) -> &N { &self.node_space }
+    pub fn edge_space(&self) -> Option<&E> { self.edge_space.as_ref() }
+    pub fn max_nodes(&self) -> usize { self.max_nodes }
+}
+
+impl<N: Space, E: Space> Space for GraphSpace<N, E> {
+    type Element = GraphInstance<N::Element, E::Element>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        let n = rng.gen_range(1..=self.max_nodes);
+        let nodes = (0..n).map(|_| self.node_space.sample(rng)).collect();
+        let num_edges = rng.gen_range(0..=n * (n - 1));
+        let edge_links = (0..num_edges).map(|_| [rng.gen_range(0..n), rng.gen_range(0..n)]).collect();
+        let edges = self.edge_space.as_ref().map(|s| (0..num_edges).map(|_| s.sample(rng)).collect());
+        GraphInstance { nodes, edges, edge_links }
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool {
+        let n = elem.nodes.len();
+        let edges_ok = match (&self.edge_space, &elem.edges) {
+            (Some(space), Some(edges)) => edges.len() == elem.edge_links.len() && edges.iter().all(|e| space.contains(e)),
+            (None, None) => true,
+            _ => false,
+        };
+        edges_ok
+            && elem.nodes.iter().all(|x| self.node_space.contains(x))
+            && elem.edge_links.iter().all(|&[a, b]| a < n && b < n)
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        for (i, x) in elem.nodes.iter().enumerate() {
+            self.node_space.validate(x).map_err(|err| nested(format_args!("GraphSpace node {i}"), err))?;
+        }
+        match (&self.edge_space, &elem.edges) {
+            (Some(space), Some(edges)) => {
+                if edges.len() != elem.edge_links.len() {
+                    return Err(GymError::OutOfSpace(format!(
+                        "GraphSpace: {} edge features for {} edge links",
+                        edges.len(),
+                        elem.edge_links.len()
+                    )));
+                }
+                for (i, e) in edges.iter().enumerate() {
+                    space.validate(e).map_err(|err| nested(format_args!("GraphSpace edge {i}"), err))?;
+                }
+            }
+            (None, None) => {}
+            (Some(_), None) => return Err(GymError::OutOfSpace("GraphSpace: edge features are missing".into())),
+            (None, Some(_)) => {
+                return Err(GymError::OutOfSpace("GraphSpace: edge features given for a space without them".into()));
+            }
+        }
+        let n = elem.nodes.len();
+        match elem.edge_links.iter().position(|&[a, b]| a >= n || b >= n) {
+            Some(i) => Err(GymError::OutOfSpace(format!(
+                "GraphSpace edge link {i}: {:?} references a node outside [0, {n})",
+                elem.edge_links[i]
+            ))),
+            None => Ok(()),
+        }
+    }
+}
@@ -1,17 +1,15 @@
 //! Numeric Backends Interop (Step 10 of README)
-//! Optional conversions between BoxSpace payloads and ndarray/nalgebra types.
+//! Optional conversions between BoxSpace/ShapedBoxSpace payloads and ndarray/nalgebra types.
 //!
 //! This module is intentionally small and fully gated behind feature flags
 //! to avoid hard dependencies. The core crate continues to use plain arrays
 //! (e.g., `[T; N]`) for BoxSpace elements.
 
-use crate::spaces::BoxSpace;
-
 // ndarray interop
 #[cfg(feature = "ndarray")]
 pub mod ndarray_impl {
-    use super::*;
-    use ndarray::Array1;
+    use crate::spaces::{BoxSpace, ShapedBoxSpace};
+    use ndarray::{Array1, ArrayD, IxDyn};
 
     /// Error type for conversions from ndarray to fixed-size arrays.
     #[derive(Debug, Clone)]
@@ -30,12 +28,26 @@ pub mod ndarray_impl {
             vec.try_into().map_err(|_| NdarrayShapeError)
         }
     }
+
+    impl<T: Copy + PartialOrd> ShapedBoxSpace<T> {
+        /// Convert a flat element into an `ndarray::ArrayD<T>` with this space's shape.
+        pub fn to_ndarray(&self, elem: Vec<T>) -> Result<ArrayD<T>, NdarrayShapeError> {
+            ArrayD::from_shape_vec(IxDyn(self.shape()), elem).map_err(|_| NdarrayShapeError)
+        }
+
+        /// Flatten an `ndarray::ArrayD<T>` (in logical row-major order) into an element,
+        /// checking that its shape matches this space's shape.
+        pub fn from_ndarray(&self, arr: &ArrayD<T>) -> Result<Vec<T>, NdarrayShapeError> {
+            if arr.shape() != self.shape() { return Err(NdarrayShapeError); }
+            Ok(arr.iter().copied().collect())
+        }
+    }
 }
 
 // nalgebra interop
 #[cfg(feature = "nalgebra")]
 pub mod nalgebra_impl {
-    use super::*;
+    use crate::spaces::BoxSpace;
     use nalgebra::SVector;
 
     impl<T: nalgebra::Scalar + Copy + PartialOrd, const N: usize> BoxSpace<T, N> {
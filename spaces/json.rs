@@ -0,0 +1,717 @@
+// Serde support and JSON Schema for spaces (feature "serde").
+//
+// Spaces serialize as objects tagged with their Gymnasium class name and holding its
+// constructor arguments, e.g. `{"type": "Discrete", "n": 3, "start": 0}`. Box bounds are flat
+// row-major arrays, with infinite bounds written as the strings "inf" and "-inf".
+// Deserialization re-checks the invariants the constructors assert and reports violations as
+// errors instead of panicking.
+
+use std::collections::BTreeMap;
+
+use serde::de;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use super::{
+    BitVector, BoxScalar, BoxSpace, DictSpace, Discrete, DynConvert, DynSpace, GraphSpace, MultiBinary, MultiDiscrete,
+    OneOf, PackedMultiBinary, SequenceSpace, ShapedBoxSpace, Space, TextSpace, TupleSpace,
+};
+
+/// Serialize a map with the "type" tag followed by the given entries.
+macro_rules! tagged {
+    ($serializer:expr, $tag:literal, { $($key:literal: $value:expr),* $(,)? }) => {{
+        let mut map = $serializer.serialize_map(None)?;
+        map.serialize_entry("type", $tag)?;
+        $(map.serialize_entry($key, $value)?;)*
+        map.end()
+    }};
+}
+
+/// Deserialize the representation `R`, then build the space from it.
+fn deserialize_repr<'de, D, R, T>(deserializer: D, build: impl FnOnce(R) -> Result<T, String>) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    R: Deserialize<'de>,
+{
+    build(R::deserialize(deserializer)?).map_err(de::Error::custom)
+}
+
+fn check_tag(found: &str, expected: &str) -> Result<(), String> {
+    ensure(found == expected, || format!("expected a {expected} space, found type {found:?}"))
+}
+
+fn ensure(ok: bool, message: impl FnOnce() -> String) -> Result<(), String> {
+    if ok { Ok(()) } else { Err(message()) }
+}
+
+impl Serialize for Discrete {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "Discrete", { "n": &self.n, "start": &self.start })
+    }
+}
+
+impl<'de> Deserialize<'de> for Discrete {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr {
+            #[serde(rename = "type")]
+            tag: String,
+            n: u32,
+            #[serde(default)]
+            start: u32,
+        }
+        deserialize_repr(deserializer, |r: Repr| {
+            check_tag(&r.tag, "Discrete")?;
+            ensure(r.n > 0, || "Discrete requires n > 0".into())?;
+            ensure(r.start.checked_add(r.n - 1).is_some(), || "Discrete start + n overflows u32".into())?;
+            Ok(Discrete::with_start(r.n, r.start))
+        })
+    }
+}
+
+impl Serialize for MultiBinary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "MultiBinary", { "n": &self.n })
+    }
+}
+
+impl<'de> Deserialize<'de> for MultiBinary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr {
+            #[serde(rename = "type")]
+            tag: String,
+            n: usize,
+        }
+        deserialize_repr(deserializer, |r: Repr| {
+            check_tag(&r.tag, "MultiBinary")?;
+            ensure(r.n > 0, || "MultiBinary requires n > 0".into())?;
+            Ok(MultiBinary::new(r.n))
+        })
+    }
+}
+
+/// Same form as `MultiBinary`: the packing only changes the elements.
+impl Serialize for PackedMultiBinary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> { self.unpacked().serialize(serializer) }
+}
+
+impl<'de> Deserialize<'de> for PackedMultiBinary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        MultiBinary::deserialize(deserializer).map(|s| s.packed())
+    }
+}
+
+/// `{"len": n, "words": [...]}` with the packed `u64` words, so large vectors stay compact.
+impl Serialize for BitVector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("len", &self.len())?;
+        map.serialize_entry("words", self.words())?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for BitVector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr {
+            len: usize,
+            words: Vec<u64>,
+        }
+        deserialize_repr(deserializer, |r: Repr| {
+            let expected = r.len.div_ceil(64);
+            ensure(r.words.len() == expected, || format!("{} bits need {expected} words, got {}", r.len, r.words.len()))?;
+            let bits = BitVector::from_words(r.words.clone(), r.len);
+            ensure(bits.words() == r.words, || format!("bits past len {} are set", r.len))?;
+            Ok(bits)
+        })
+    }
+}
+
+impl Serialize for MultiDiscrete {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "MultiDiscrete", { "nvec": &self.nvec, "start": &self.start })
+    }
+}
+
+impl<'de> Deserialize<'de> for MultiDiscrete {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr {
+            #[serde(rename = "type")]
+            tag: String,
+            nvec: Vec<u32>,
+            start: Option<Vec<u32>>,
+        }
+        deserialize_repr(deserializer, |r: Repr| {
+            check_tag(&r.tag, "MultiDiscrete")?;
+            let start = r.start.unwrap_or_else(|| vec![0; r.nvec.len()]);
+            ensure(!r.nvec.is_empty(), || "MultiDiscrete requires at least one dimension".into())?;
+            ensure(start.len() == r.nvec.len(), || "MultiDiscrete start must have one entry per dimension".into())?;
+            for (i, (&n, &s)) in r.nvec.iter().zip(&start).enumerate() {
+                ensure(n > 0, || format!("MultiDiscrete nvec[{i}] must be > 0"))?;
+                ensure(s.checked_add(n - 1).is_some(), || format!("MultiDiscrete start[{i}] + nvec[{i}] overflows u32"))?;
+            }
+            Ok(MultiDiscrete::with_start(r.nvec, start))
+        })
+    }
+}
+
+/// Box bounds as a JSON array; infinities become "inf" / "-inf".
+struct Bounds<'a, T>(&'a [T]);
+
+impl<T: BoxScalar + Serialize> Serialize for Bounds<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for &v in self.0 {
+            if v.is_finite() {
+                seq.serialize_element(&v)?;
+            } else {
+                seq.serialize_element(if v.to_f32() > 0.0 { "inf" } else { "-inf" })?;
+            }
+        }
+        seq.end()
+    }
+}
+
+/// One deserialized Box bound: a number, or "inf" / "-inf" for float dtypes.
+struct Bound<T>(T);
+
+impl<'de, T: BoxScalar + Deserialize<'de>> Deserialize<'de> for Bound<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Value(T),
+            Text(String),
+        }
+        deserialize_repr(deserializer, |r: Repr<T>| match r {
+            Repr::Value(v) => Ok(Bound(v)),
+            Repr::Text(text) => {
+                let v = match text.as_str() {
+                    "inf" | "+inf" | "infinity" => T::from_f32(f32::INFINITY),
+                    "-inf" | "-infinity" => T::from_f32(f32::NEG_INFINITY),
+                    _ => return Err(format!("invalid Box bound {text:?}")),
+                };
+                ensure(!v.is_finite(), || format!("{} bounds cannot be infinite", T::DTYPE))?;
+                Ok(Bound(v))
+            }
+        })
+    }
+}
+
+/// Gymnasium accepts a scalar bound for the whole Box; it is broadcast to every element.
+#[derive(Deserialize)]
+#[serde(untagged, bound(deserialize = "T: BoxScalar + Deserialize<'de>"))]
+enum BoundsRepr<T> {
+    Scalar(Bound<T>),
+    Array(Vec<Bound<T>>),
+}
+
+impl<T: BoxScalar> BoundsRepr<T> {
+    fn expand(self, len: usize, which: &str) -> Result<Vec<T>, String> {
+        match self {
+            BoundsRepr::Scalar(Bound(v)) => Ok(vec![v; len]),
+            BoundsRepr::Array(values) => {
+                ensure(values.len() == len, || format!("Box {which} has {} values, expected {len}", values.len()))?;
+                Ok(values.into_iter().map(|Bound(v)| v).collect())
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound(deserialize = "T: BoxScalar + Deserialize<'de>"))]
+struct BoxRepr<T> {
+    #[serde(rename = "type")]
+    tag: String,
+    shape: Vec<usize>,
+    dtype: String,
+    low: BoundsRepr<T>,
+    high: BoundsRepr<T>,
+}
+
+impl<T: BoxScalar> TryFrom<BoxRepr<T>> for ShapedBoxSpace<T> {
+    type Error = String;
+
+    fn try_from(r: BoxRepr<T>) -> Result<Self, String> {
+        check_tag(&r.tag, "Box")?;
+        ensure(r.dtype == T::DTYPE, || format!("Box dtype {} does not match {}", r.dtype, T::DTYPE))?;
+        let len = r.shape.iter().product();
+        let (low, high) = (r.low.expand(len, "low")?, r.high.expand(len, "high")?);
+        if let Some(i) = (0..len).find(|&i| low[i] > high[i]) {
+            return Err(format!("Box low[{i}] {:?} > high[{i}] {:?}", low[i], high[i]));
+        }
+        Ok(ShapedBoxSpace::new(r.shape, low, high))
+    }
+}
+
+impl<T: BoxScalar + Serialize> Serialize for ShapedBoxSpace<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "Box", {
+            "shape": self.shape(),
+            "dtype": T::DTYPE,
+            "low": &Bounds(self.low()),
+            "high": &Bounds(self.high()),
+        })
+    }
+}
+
+impl<'de, T: BoxScalar + Deserialize<'de>> Deserialize<'de> for ShapedBoxSpace<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_repr(deserializer, |r: BoxRepr<T>| ShapedBoxSpace::try_from(r))
+    }
+}
+
+impl<T: BoxScalar + Serialize, const N: usize> Serialize for BoxSpace<T, N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "Box", {
+            "shape": &[N],
+            "dtype": T::DTYPE,
+            "low": &Bounds(self.low()),
+            "high": &Bounds(self.high()),
+        })
+    }
+}
+
+/// Accepts the Box representation with shape `[N]`.
+impl<'de, T: BoxScalar + Deserialize<'de>, const N: usize> Deserialize<'de> for BoxSpace<T, N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_repr(deserializer, |r: BoxRepr<T>| {
+            BoxSpace::try_from(ShapedBoxSpace::try_from(r)?).map_err(|e| e.to_string())
+        })
+    }
+}
+
+/// Plain tuples of spaces serialize as bare arrays (serde's tuple impls); `TupleSpace` adds the tag.
+impl<S: Space + Serialize> Serialize for TupleSpace<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        tagged!(serializer, "Tuple", { "spaces": self.spaces() })
+    }
+}
+
+impl<'de, S: Space + Deserialize<'de>> Deserialize<'de> for TupleSpace<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<S> {
+            #[serde(rename = "type")]
+            tag: String,
+            spaces: S,
+        }
+        deserialize_repr(deserializer, |r: Repr<S>| {
+            check_tag(&r.tag, "Tuple")?;
+            Ok(TupleSpace::new(r.spaces))
+        })
+    }
+}
+
+impl<S: Space + Serialize> Serialize for DictSpace<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        tagged!(serializer, "Dict", { "spaces": self.spaces() })
+    }
+}
+
+impl<'de, S: Space + Deserialize<'de>> Deserialize<'de> for DictSpace<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<S> {
+            #[serde(rename = "type")]
+            tag: String,
+            spaces: BTreeMap<String, S>,
+        }
+        deserialize_repr(deserializer, |r: Repr<S>| {
+            check_tag(&r.tag, "Dict")?;
+            ensure(!r.spaces.is_empty(), || "DictSpace requires at least one subspace".into())?;
+            Ok(DictSpace::new(r.spaces))
+        })
+    }
+}
+
+impl Serialize for TextSpace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "Text", {
+            "min_length": &self.min_length(),
+            "max_length": &self.max_length(),
+            "charset": &self.charset().iter().collect::<String>(),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for TextSpace {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr {
+            #[serde(rename = "type")]
+            tag: String,
+            #[serde(default)]
+            min_length: usize,
+            max_length: usize,
+            charset: Option<String>,
+        }
+        deserialize_repr(deserializer, |r: Repr| {
+            check_tag(&r.tag, "Text")?;
+            ensure(r.min_length <= r.max_length, || "TextSpace: min_length > max_length".into())?;
+            Ok(match r.charset {
+                Some(charset) => {
+                    ensure(!charset.is_empty() || r.max_length == 0, || "TextSpace requires a non-empty charset".into())?;
+                    TextSpace::with_charset(r.min_length, r.max_length, charset.chars())
+                }
+                None => TextSpace::new(r.min_length, r.max_length),
+            })
+        })
+    }
+}
+
+impl<S: Space + Serialize> Serialize for SequenceSpace<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        tagged!(serializer, "Sequence", {
+            "space": self.space(),
+            "min_length": &self.min_length(),
+            "max_length": &self.max_length(),
+        })
+    }
+}
+
+impl<'de, S: Space + Deserialize<'de>> Deserialize<'de> for SequenceSpace<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<S> {
+            #[serde(rename = "type")]
+            tag: String,
+            space: S,
+            #[serde(default)]
+            min_length: usize,
+            max_length: Option<usize>,
+        }
+        deserialize_repr(deserializer, |r: Repr<S>| {
+            check_tag(&r.tag, "Sequence")?;
+            match r.max_length {
+                Some(max) => {
+                    ensure(r.min_length <= max, || "SequenceSpace: min_length > max_length".into())?;
+                    Ok(SequenceSpace::bounded(r.space, r.min_length, max))
+                }
+                None => {
+                    ensure(r.min_length == 0, || "SequenceSpace: min_length requires max_length".into())?;
+                    Ok(SequenceSpace::new(r.space))
+                }
+            }
+        })
+    }
+}
+
+impl<N: Space + Serialize, E: Space + Serialize> Serialize for GraphSpace<N, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        tagged!(serializer, "Graph", {
+            "node_space": self.node_space(),
+            "edge_space": &self.edge_space(),
+            "max_nodes": &self.max_nodes(),
+        })
+    }
+}
+
+impl<'de, N: Space + Deserialize<'de>, E: Space + Deserialize<'de>> Deserialize<'de> for GraphSpace<N, E> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<N, E> {
+            #[serde(rename = "type")]
+            tag: String,
+            node_space: N,
+            edge_space: Option<E>,
+            max_nodes: Option<usize>,
+        }
+        deserialize_repr(deserializer, |r: Repr<N, E>| {
+            check_tag(&r.tag, "Graph")?;
+            let space = GraphSpace::new(r.node_space, r.edge_space);
+            match r.max_nodes {
+                Some(max) => {
+                    ensure(max > 0, || "GraphSpace requires max_nodes > 0".into())?;
+                    Ok(space.with_max_nodes(max))
+                }
+                None => Ok(space),
+            }
+        })
+    }
+}
+
+impl<S: Space + Serialize> Serialize for OneOf<S> {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        tagged!(serializer, "OneOf", { "spaces": self.spaces() })
+    }
+}
+
+impl<'de, S: Space + Deserialize<'de>> Deserialize<'de> for OneOf<S> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Repr<S> {
+            #[serde(rename = "type")]
+            tag: String,
+            spaces: Vec<S>,
+        }
+        deserialize_repr(deserializer, |r: Repr<S>| {
+            check_tag(&r.tag, "OneOf")?;
+            ensure(!r.spaces.is_empty(), || "OneOf requires at least one subspace".into())?;
+            Ok(OneOf::new(r.spaces))
+        })
+    }
+}
+
+impl Serialize for DynSpace {
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        match self {
+            DynSpace::Discrete(s) => s.serialize(serializer),
+            DynSpace::MultiBinary(s) => s.serialize(serializer),
+            DynSpace::MultiDiscrete(s) => s.serialize(serializer),
+            DynSpace::Box(s) => s.serialize(serializer),
+            DynSpace::IntBox(s) => s.serialize(serializer),
+            DynSpace::Text(s) => s.serialize(serializer),
+            DynSpace::Tuple(spaces) => tagged!(serializer, "Tuple", { "spaces": spaces }),
+            DynSpace::Dict(s) => s.serialize(serializer),
+            DynSpace::Sequence(s) => s.serialize(serializer),
+            DynSpace::Graph(s) => s.serialize(serializer),
+            DynSpace::OneOf(s) => s.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DynSpace {
+    /// Dispatches on the "type" tag (and on "dtype" for Box, which is widened to `f64` or `i64`).
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        fn typed<S: DynConvert + de::DeserializeOwned>(value: Value) -> serde_json::Result<DynSpace> {
+            serde_json::from_value::<S>(value).map(|s| s.to_dyn_space())
+        }
+        let value = Value::deserialize(deserializer)?;
+        let tag = value.get("type").and_then(Value::as_str).ok_or_else(|| de::Error::missing_field("type"))?;
+        let space = match tag {
+            "Discrete" => typed::<Discrete>(value),
+            "MultiBinary" => typed::<MultiBinary>(value),
+            "MultiDiscrete" => typed::<MultiDiscrete>(value),
+            "Box" => match value.get("dtype").and_then(Value::as_str).ok_or_else(|| de::Error::missing_field("dtype"))? {
+                "float32" => typed::<ShapedBoxSpace<f32>>(value),
+                "float64" => typed::<ShapedBoxSpace<f64>>(value),
+                "uint8" => typed::<ShapedBoxSpace<u8>>(value),
+                "uint16" => typed::<ShapedBoxSpace<u16>>(value),
+                "uint32" => typed::<ShapedBoxSpace<u32>>(value),
+                "uint64" => typed::<ShapedBoxSpace<u64>>(value),
+                "int8" => typed::<ShapedBoxSpace<i8>>(value),
+                "int16" => typed::<ShapedBoxSpace<i16>>(value),
+                "int32" => typed::<ShapedBoxSpace<i32>>(value),
+                "int64" => typed::<ShapedBoxSpace<i64>>(value),
+                other => return Err(de::Error::custom(format!("unsupported Box dtype {other:?}"))),
+            },
+            "Text" => typed::<TextSpace>(value),
+            "Tuple" => {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Repr {
+                    #[serde(rename = "type")]
+                    #[allow(dead_code)]
+                    tag: String,
+                    spaces: Vec<DynSpace>,
+                }
+                serde_json::from_value::<Repr>(value).map(|r| DynSpace::Tuple(r.spaces))
+            }
+            "Dict" => serde_json::from_value(value).map(DynSpace::Dict),
+            "Sequence" => serde_json::from_value(value).map(|s| DynSpace::Sequence(Box::new(s))),
+            "Graph" => serde_json::from_value(value).map(|s| DynSpace::Graph(Box::new(s))),
+            "OneOf" => serde_json::from_value(value).map(DynSpace::OneOf),
+            other => return Err(de::Error::custom(format!("unknown space type {other:?}"))),
+        };
+        space.map_err(de::Error::custom)
+    }
+}
+
+/// Spaces that can describe the JSON form of their elements (as produced by serde) with a
+/// JSON Schema (draft 2020-12).
+///
+/// The schema checks types, lengths, keys and bounds; constraints across values (edge links
+/// referencing existing nodes in a `GraphSpace`) are left to `Space::validate`.
+/// Infinite Box bounds are omitted, as JSON has no infinite numbers.
+pub trait ElementSchema: Space {
+    /// Schema of one element, without the `$schema` keyword.
+    fn element_schema(&self) -> Value;
+}
+
+/// A standalone JSON Schema document for the elements of `space`.
+pub fn json_schema<S: ElementSchema>(space: &S) -> Value {
+    let mut schema = space.element_schema();
+    if let Value::Object(map) = &mut schema {
+        map.insert("$schema".into(), json!("https://json-schema.org/draft/2020-12/schema"));
+    }
+    schema
+}
+
+/// Schema of a fixed-length array whose items follow `items` in order.
+fn fixed_array(items: Vec<Value>) -> Value {
+    json!({ "type": "array", "prefixItems": items, "items": false, "minItems": items.len() })
+}
+
+/// Schema of a fixed-length array whose items all follow `item`.
+fn uniform_array(item: Value, len: usize) -> Value {
+    json!({ "type": "array", "items": item, "minItems": len, "maxItems": len })
+}
+
+fn discrete_schema(n: u32, start: u32) -> Value {
+    json!({ "type": "integer", "minimum": start, "maximum": start + (n - 1) })
+}
+
+fn scalar_schema<T: BoxScalar + Serialize>(low: T, high: T) -> Value {
+    let mut schema = json!({ "type": if T::DTYPE.starts_with("float") { "number" } else { "integer" } });
+    for (key, bound) in [("minimum", low), ("maximum", high)] {
+        if bound.is_finite() {
+            schema[key] = serde_json::to_value(bound).unwrap_or(Value::Null);
+        }
+    }
+    schema
+}
+
+/// Schema of a Box element; identical bounds everywhere collapse into a single item schema.
+fn box_schema<T: BoxScalar + Serialize>(low: &[T], high: &[T]) -> Value {
+    let uniform = low.windows(2).all(|w| w[0] == w[1]) && high.windows(2).all(|w| w[0] == w[1]);
+    match (low.first(), high.first()) {
+        (Some(&lo), Some(&hi)) if uniform => uniform_array(scalar_schema(lo, hi), low.len()),
+        _ => fixed_array(low.iter().zip(high).map(|(&lo, &hi)| scalar_schema(lo, hi)).collect()),
+    }
+}
+
+impl ElementSchema for Discrete {
+    fn element_schema(&self) -> Value { discrete_schema(self.n, self.start) }
+}
+
+impl ElementSchema for MultiBinary {
+    fn element_schema(&self) -> Value { uniform_array(json!({ "type": "integer", "enum": [0, 1] }), self.n) }
+}
+
+impl ElementSchema for PackedMultiBinary {
+    fn element_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "len": { "const": self.n() },
+                "words": uniform_array(json!({ "type": "integer", "minimum": 0 }), self.n().div_ceil(64)),
+            },
+            "required": ["len", "words"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ElementSchema for MultiDiscrete {
+    fn element_schema(&self) -> Value {
+        fixed_array(self.nvec.iter().zip(&self.start).map(|(&n, &s)| discrete_schema(n, s)).collect())
+    }
+}
+
+impl<T: BoxScalar + Serialize, const N: usize> ElementSchema for BoxSpace<T, N> {
+    fn element_schema(&self) -> Value { box_schema(self.low(), self.high()) }
+}
+
+impl<T: BoxScalar + Serialize> ElementSchema for ShapedBoxSpace<T> {
+    fn element_schema(&self) -> Value { box_schema(self.low(), self.high()) }
+}
+
+impl<S: ElementSchema> ElementSchema for TupleSpace<S> {
+    fn element_schema(&self) -> Value { self.spaces().element_schema() }
+}
+
+macro_rules! schema_tuple {
+    ($($S:ident $i:tt),+) => {
+        impl<$($S: ElementSchema),+> ElementSchema for ($($S,)+) {
+            fn element_schema(&self) -> Value { fixed_array(vec![$(self.$i.element_schema()),+]) }
+        }
+    };
+}
+
+schema_tuple!(A 0);
+schema_tuple!(A 0, B 1);
+schema_tuple!(A 0, B 1, C 2);
+schema_tuple!(A 0, B 1, C 2, D 3);
+schema_tuple!(A 0, B 1, C 2, D 3, E 4);
+schema_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+schema_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+schema_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<S: ElementSchema> ElementSchema for DictSpace<S> {
+    fn element_schema(&self) -> Value {
+        let properties: serde_json::Map<String, Value> =
+            self.iter().map(|(k, s)| (k.to_string(), s.element_schema())).collect();
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": self.keys().collect::<Vec<_>>(),
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl ElementSchema for TextSpace {
+    fn element_schema(&self) -> Value {
+        // A character class of the charset, escaping the characters special inside `[...]`.
+        let class: String = self
+            .charset()
+            .iter()
+            .flat_map(|&c| if matches!(c, '\\' | ']' | '[' | '^' | '-') { vec!['\\', c] } else { vec![c] })
+            .collect();
+        let pattern = if class.is_empty() { "^$".to_string() } else { format!("^[{class}]*$") };
+        json!({
+            "type": "string",
+            "minLength": self.min_length(),
+            "maxLength": self.max_length(),
+            "pattern": pattern,
+        })
+    }
+}
+
+impl<S: ElementSchema> ElementSchema for SequenceSpace<S> {
+    fn element_schema(&self) -> Value {
+        let mut schema = json!({ "type": "array", "items": self.space().element_schema(), "minItems": self.min_length() });
+        if let Some(max) = self.max_length() {
+            schema["maxItems"] = json!(max);
+        }
+        schema
+    }
+}
+
+impl<N: ElementSchema, E: ElementSchema> ElementSchema for GraphSpace<N, E> {
+    fn element_schema(&self) -> Value {
+        let index = json!({ "type": "integer", "minimum": 0 });
+        let edges = match self.edge_space() {
+            Some(space) => json!({ "type": "array", "items": space.element_schema() }),
+            None => json!({ "type": "null" }),
+        };
+        json!({
+            "type": "object",
+            "properties": {
+                "nodes": { "type": "array", "items": self.node_space().element_schema() },
+                "edges": edges,
+                "edge_links": { "type": "array", "items": fixed_array(vec![index.clone(), index]) },
+            },
+            "required": ["nodes", "edges", "edge_links"],
+            "additionalProperties": false,
+        })
+    }
+}
+
+impl<S: ElementSchema> ElementSchema for OneOf<S> {
+    /// Elements are `[index, value]` pairs.
+    fn element_schema(&self) -> Value {
+        let variants: Vec<Value> = self
+            .spaces()
+            .iter()
+            .enumerate()
+            .map(|(i, s)| fixed_array(vec![json!({ "const": i }), s.element_schema()]))
+            .collect();
+        json!({ "oneOf": variants })
+    }
+}
@@ -0,0 +1,55 @@
+// Action masks for discrete spaces (Gymnasium's `mask` argument of `sample`).
+
+use crate::core::{Info, InfoValue};
+
+/// Info key under which environments report the valid actions of the next step.
+pub const ACTION_MASK_KEY: &str = "action_mask";
+
+/// Which actions of a `Discrete` space (or one `MultiDiscrete` dimension) are currently valid.
+///
+/// Envs report it in `Info` with `insert_into`, stored as `InfoValue::Bytes` of 0/1 like
+/// Gymnasium's int8 masks; agents read it back with `from_info` and pass it to `sample_masked`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ActionMask {
+    allowed: Vec<bool>,
+}
+
+impl ActionMask {
+    pub fn new<V: Into<Vec<bool>>>(allowed: V) -> Self { Self { allowed: allowed.into() } }
+
+    /// A mask of `n` actions, all valid.
+    pub fn all(n: usize) -> Self { Self { allowed: vec![true; n] } }
+
+    /// A mask of `n` actions where `f(i)` tells whether action `i` is valid.
+    pub fn from_fn<F: FnMut(usize) -> bool>(n: usize, f: F) -> Self { Self { allowed: (0..n).map(f).collect() } }
+
+    pub fn len(&self) -> usize { self.allowed.len() }
+    pub fn is_empty(&self) -> bool { self.allowed.is_empty() }
+    pub fn as_slice(&self) -> &[bool] { &self.allowed }
+
+    /// Whether action `i` is valid; out-of-range actions are not.
+    pub fn is_allowed(&self, i: usize) -> bool { self.allowed.get(i).copied().unwrap_or(false) }
+
+    /// Mark action `i` as valid or invalid. Panics if `i` is out of range.
+    pub fn set(&mut self, i: usize, allowed: bool) { self.allowed[i] = allowed; }
+
+    /// Number of valid actions.
+    pub fn count_allowed(&self) -> usize { self.allowed.iter().filter(|&&a| a).count() }
+
+    /// Indices of the valid actions, in increasing order.
+    pub fn allowed(&self) -> impl Iterator<Item = usize> + '_ {
+        self.allowed.iter().enumerate().filter_map(|(i, &a)| a.then_some(i))
+    }
+
+    /// Store the mask under `ACTION_MASK_KEY`.
+    pub fn insert_into(&self, info: &mut Info) { info.insert(ACTION_MASK_KEY, InfoValue::from(self.clone())); }
+
+    /// The mask stored under `ACTION_MASK_KEY`, if any; non-zero bytes are valid actions.
+    pub fn from_info(info: &Info) -> Option<Self> {
+        info.get_bytes(ACTION_MASK_KEY).map(|b| Self { allowed: b.iter().map(|&v| v != 0).collect() })
+    }
+}
+
+impl From<ActionMask> for InfoValue {
+    fn from(mask: ActionMask) -> Self { InfoValue::Bytes(mask.allowed.into_iter().map(u8::from).collect()) }
+}
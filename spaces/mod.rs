@@ -1,40 +1,105 @@
-/// Space implementations (Step 4 of README)
+// Space implementations (Step 4 of README)
 
 pub mod space;
 #[path = "interop.rs"]
 pub mod interop;
+#[path = "tuple.rs"]
+pub mod tuple;
+#[path = "dict.rs"]
+pub mod dict;
+#[path = "shaped_box.rs"]
+pub mod shaped_box;
+#[path = "mask.rs"]
+pub mod mask;
+#[path = "text.rs"]
+pub mod text;
+#[path = "sequence.rs"]
+pub mod sequence;
+#[path = "graph.rs"]
+pub mod graph;
+#[path = "oneof.rs"]
+pub mod oneof;
+#[path = "dynamic.rs"]
+pub mod dynamic;
+#[path = "distributions.rs"]
+pub mod distributions;
+#[path = "bits.rs"]
+pub mod bits;
+#[path = "utils.rs"]
+pub mod utils;
+#[cfg(feature = "serde")]
+#[path = "json.rs"]
+pub mod json;
 
+use rand::distributions::uniform::SampleUniform;
 use rand::distributions::{Distribution, Uniform};
 use rand::Rng;
 
-pub use space::Space;
+use crate::core::{GymError, Result};
 
-/// A discrete space of integers in [0, n).
+pub use space::{Observation, Space};
+pub use tuple::TupleSpace;
+pub use dict::DictSpace;
+pub use shaped_box::ShapedBoxSpace;
+pub use mask::{ActionMask, ACTION_MASK_KEY};
+pub use text::TextSpace;
+pub use sequence::SequenceSpace;
+pub use graph::{GraphInstance, GraphSpace};
+pub use oneof::OneOf;
+pub use dynamic::{DynConvert, DynSpace, DynValue};
+pub use distributions::{BoxDistribution, Categorical, MultiBernoulli, MultiCategorical};
+pub use bits::{BitVector, PackedMultiBinary};
+pub use utils::{flatdim, flatten, flatten_space, unflatten, Flatten};
+#[cfg(feature = "serde")]
+pub use json::{json_schema, ElementSchema};
+
+/// A discrete space of integers in [start, start + n) (Gymnasium's `Discrete(n, start)`).
+/// Elements are `u32`, so the start offset is non-negative.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Discrete {
     n: u32,
+    start: u32,
 }
 
 impl Discrete {
-    pub fn new(n: u32) -> Self {
+    pub fn new(n: u32) -> Self { Self::with_start(n, 0) }
+
+    /// The space {start, ..., start + n - 1}.
+    pub fn with_start(n: u32, start: u32) -> Self {
         assert!(n > 0, "Discrete space requires n > 0");
-        Self { n }
+        assert!(start.checked_add(n - 1).is_some(), "Discrete start + n overflows u32");
+        Self { n, start }
     }
 
     pub fn n(&self) -> u32 { self.n }
+    pub fn start(&self) -> u32 { self.start }
+
+    /// Sample uniformly among the actions `mask` allows; `None` if it allows none.
+    /// Mask entry `i` stands for the value `start + i`.
+    /// Panics if the mask length differs from `n`.
+    pub fn sample_masked<R: Rng + ?Sized>(&self, rng: &mut R, mask: &ActionMask) -> Option<u32> {
+        assert_eq!(mask.len(), self.n as usize, "Discrete({}) needs a mask of {} actions", self.n, self.n);
+        let count = mask.count_allowed();
+        if count == 0 { return None; }
+        mask.allowed().nth(rng.gen_range(0..count)).map(|i| self.start + i as u32)
+    }
 }
 
 impl Space for Discrete {
     type Element = u32;
 
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
-        // Uniform over [0, n)
-        if self.n == 1 { return 0; }
+        // Uniform over [start, start + n)
+        if self.n == 1 { return self.start; }
         let dist = Uniform::from(0..self.n);
-        dist.sample(rng)
+        self.start + dist.sample(rng)
     }
 
-    fn contains(&self, elem: &Self::Element) -> bool { *elem < self.n }
+    fn contains(&self, elem: &Self::Element) -> bool { elem.wrapping_sub(self.start) < self.n }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        check_discrete("Discrete", *elem, self.n, self.start)
+    }
 }
 
 /// A fixed-length binary vector space of size `n`.
@@ -65,26 +130,57 @@ impl Space for MultiBinary {
     fn contains(&self, elem: &Self::Element) -> bool {
         elem.len() == self.n && elem.iter().all(|&v| v == 0 || v == 1)
     }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        check_len("MultiBinary", elem.len(), self.n)?;
+        match elem.iter().position(|&v| v > 1) {
+            Some(i) => Err(GymError::OutOfSpace(format!("MultiBinary dim {i}: {} is not 0 or 1", elem[i]))),
+            None => Ok(()),
+        }
+    }
 }
 
-/// A multi-dimensional discrete space with per-dimension sizes nvec[i] (values in [0, nvec[i])).
+/// A multi-dimensional discrete space with per-dimension sizes nvec[i] and offsets start[i]
+/// (values in [start[i], start[i] + nvec[i])).
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct MultiDiscrete {
     nvec: Vec<u32>,
+    start: Vec<u32>,
 }
 
 impl MultiDiscrete {
     pub fn new<I: Into<Vec<u32>>>(nvec: I) -> Self {
         let nvec = nvec.into();
+        let start = vec![0; nvec.len()];
+        Self::with_start(nvec, start)
+    }
+
+    /// Per-dimension offsets, as in Gymnasium's `MultiDiscrete(nvec, start)`.
+    pub fn with_start<I: Into<Vec<u32>>, J: Into<Vec<u32>>>(nvec: I, start: J) -> Self {
+        let (nvec, start) = (nvec.into(), start.into());
         assert!(!nvec.is_empty(), "MultiDiscrete requires at least one dimension");
+        assert_eq!(start.len(), nvec.len(), "MultiDiscrete start must have one entry per dimension");
         for (i, &n) in nvec.iter().enumerate() {
             assert!(n > 0, "MultiDiscrete nvec[{i}] must be > 0");
+            assert!(start[i].checked_add(n - 1).is_some(), "MultiDiscrete start[{i}] + nvec[{i}] overflows u32");
         }
-        Self { nvec }
+        Self { nvec, start }
     }
 
     pub fn nvec(&self) -> &[u32] { &self.nvec }
+    pub fn start(&self) -> &[u32] { &self.start }
     pub fn ndim(&self) -> usize { self.nvec.len() }
+
+    /// Dimension `i` as a `Discrete` space.
+    pub fn dim(&self, i: usize) -> Discrete { Discrete::with_start(self.nvec[i], self.start[i]) }
+
+    /// Sample each dimension uniformly among the values its mask allows (one mask per
+    /// dimension, as in Gymnasium); `None` if some dimension allows none.
+    /// Panics if the number of masks or a mask length does not match `nvec`.
+    pub fn sample_masked<R: Rng + ?Sized>(&self, rng: &mut R, masks: &[ActionMask]) -> Option<Vec<u32>> {
+        assert_eq!(masks.len(), self.nvec.len(), "MultiDiscrete needs one mask per dimension");
+        masks.iter().enumerate().map(|(i, mask)| self.dim(i).sample_masked(rng, mask)).collect()
+    }
 }
 
 impl Space for MultiDiscrete {
@@ -93,18 +189,157 @@ impl Space for MultiDiscrete {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
         self.nvec
             .iter()
-            .map(|&n| if n == 1 { 0 } else { Uniform::from(0..n).sample(rng) })
+            .zip(&self.start)
+            .map(|(&n, &s)| s + if n == 1 { 0 } else { Uniform::from(0..n).sample(rng) })
             .collect()
     }
 
     fn contains(&self, elem: &Self::Element) -> bool {
         if elem.len() != self.nvec.len() { return false; }
-        elem.iter().zip(self.nvec.iter()).all(|(&v, &n)| v < n)
+        elem.iter().zip(self.nvec.iter().zip(&self.start)).all(|(&v, (&n, &s))| v.wrapping_sub(s) < n)
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        check_len("MultiDiscrete", elem.len(), self.nvec.len())?;
+        for (i, (&v, (&n, &s))) in elem.iter().zip(self.nvec.iter().zip(&self.start)).enumerate() {
+            check_discrete(&format!("MultiDiscrete dim {i}"), v, n, s)?;
+        }
+        Ok(())
+    }
+}
+
+fn check_discrete(what: &str, v: u32, n: u32, start: u32) -> Result<()> {
+    if v.wrapping_sub(start) < n {
+        Ok(())
+    } else {
+        Err(GymError::OutOfSpace(format!("{what}: {v} is outside [{start}, {})", u64::from(start) + u64::from(n))))
+    }
+}
+
+/// Length check shared by the variable-size element types.
+fn check_len(what: &str, len: usize, expected: usize) -> Result<()> {
+    if len == expected {
+        Ok(())
+    } else {
+        Err(GymError::OutOfSpace(format!("{what}: expected {expected} values, got {len}")))
+    }
+}
+
+/// Prefix the error of a subspace with where in the enclosing element it occurred.
+pub(crate) fn nested(what: impl std::fmt::Display, error: GymError) -> GymError { GymError::OutOfSpace(format!("{what}: {error}")) }
+
+/// Bound check of one Box component; NaN fails both comparisons and is reported as such.
+fn check_bounds<T: BoxScalar>(what: &str, v: T, low: T, high: T) -> Result<()> {
+    if low <= v && v <= high {
+        Ok(())
+    } else if v > high {
+        Err(GymError::OutOfSpace(format!("{what}: {v:?} > high {high:?}")))
+    } else if v < low {
+        Err(GymError::OutOfSpace(format!("{what}: {v:?} < low {low:?}")))
+    } else {
+        Err(GymError::OutOfSpace(format!("{what}: {v:?} is not within [{low:?}, {high:?}]")))
     }
 }
 
+/// Element type of Box spaces. Float bounds may be infinite; integer bounds are always finite.
+pub trait BoxScalar: Copy + PartialOrd + SampleUniform + std::fmt::Debug {
+    /// NumPy name of the type, e.g. "float32", as in Gymnasium's `Box.dtype`.
+    const DTYPE: &'static str;
+
+    /// The widest bounds of a Box of this type: the integer limits, or ±infinity for floats.
+    const LOWEST: Self;
+    const HIGHEST: Self;
+
+    /// Sample one value in [low, high], following Gymnasium's `Box.sample`: uniform when both
+    /// bounds are finite, the finite bound shifted by a standard exponential when half-bounded,
+    /// and a standard normal when unbounded on both sides.
+    fn sample_between<R: Rng + ?Sized>(low: Self, high: Self, rng: &mut R) -> Self;
+
+    /// Lossy conversion used by `Flatten`.
+    fn to_f32(self) -> f32;
+
+    /// Inverse of `to_f32`; integers are rounded and saturate at their bounds.
+    fn from_f32(v: f32) -> Self;
+
+    /// Widening conversion used by `DynSpace`; integers beyond 2^53 lose precision.
+    fn to_f64(self) -> f64;
+
+    /// Inverse of `to_f64`; integers are rounded and saturate at their bounds.
+    fn from_f64(v: f64) -> Self;
+
+    /// Checked conversion from a `DynSpace` integer; `None` if it is out of range for the type.
+    fn from_i64(v: i64) -> Option<Self>;
+
+    /// Whether the value is neither infinite nor NaN; always true for integers.
+    fn is_finite(self) -> bool { true }
+}
+
+macro_rules! box_scalar_int {
+    ($($t:ty => $dtype:literal),*) => {$(
+        impl BoxScalar for $t {
+            const DTYPE: &'static str = $dtype;
+            const LOWEST: Self = <$t>::MIN;
+            const HIGHEST: Self = <$t>::MAX;
+
+            fn sample_between<R: Rng + ?Sized>(low: Self, high: Self, rng: &mut R) -> Self {
+                Uniform::new_inclusive(low, high).sample(rng)
+            }
+
+            fn to_f32(self) -> f32 { self as f32 }
+            fn from_f32(v: f32) -> Self { v.round() as $t }
+            fn to_f64(self) -> f64 { self as f64 }
+            fn from_f64(v: f64) -> Self { v.round() as $t }
+            fn from_i64(v: i64) -> Option<Self> { <$t>::try_from(v).ok() }
+        }
+    )*};
+}
+
+macro_rules! box_scalar_float {
+    ($($t:ty => $dtype:literal),*) => {$(
+        impl BoxScalar for $t {
+            const DTYPE: &'static str = $dtype;
+            const LOWEST: Self = <$t>::NEG_INFINITY;
+            const HIGHEST: Self = <$t>::INFINITY;
+
+            fn sample_between<R: Rng + ?Sized>(low: Self, high: Self, rng: &mut R) -> Self {
+                if low == high { return low; }
+                match (low.is_finite(), high.is_finite()) {
+                    (true, true) => Uniform::new_inclusive(low, high).sample(rng),
+                    (true, false) => low + standard_exponential(rng) as $t,
+                    (false, true) => high - standard_exponential(rng) as $t,
+                    (false, false) => standard_normal(rng) as $t,
+                }
+            }
+
+            fn to_f32(self) -> f32 { self as f32 }
+            fn from_f32(v: f32) -> Self { v as $t }
+            fn to_f64(self) -> f64 { self as f64 }
+            fn from_f64(v: f64) -> Self { v as $t }
+            fn from_i64(v: i64) -> Option<Self> { Some(v as $t) }
+            fn is_finite(self) -> bool { <$t>::is_finite(self) }
+        }
+    )*};
+}
+
+// usize/isize use the NumPy names of their 64-bit counterparts.
+box_scalar_int!(
+    u8 => "uint8", u16 => "uint16", u32 => "uint32", u64 => "uint64", usize => "uint64",
+    i8 => "int8", i16 => "int16", i32 => "int32", i64 => "int64", isize => "int64"
+);
+box_scalar_float!(f32 => "float32", f64 => "float64");
+
+/// Exp(1) by inversion; `1 - u` lies in (0, 1], so the logarithm is finite.
+fn standard_exponential<R: Rng + ?Sized>(rng: &mut R) -> f64 { -(1.0 - rng.r#gen::<f64>()).ln() }
+
+/// N(0, 1) by the Box-Muller transform.
+fn standard_normal<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let radius = (2.0 * standard_exponential(rng)).sqrt();
+    radius * (std::f64::consts::TAU * rng.r#gen::<f64>()).cos()
+}
+
 /// A simple Box-like space with element type `T` and fixed compile-time length `N`.
-/// Uses per-dimension inclusive lower/upper bounds for validation and sampling.
+/// Uses per-dimension inclusive lower/upper bounds for validation and sampling; float bounds may
+/// be infinite (see `BoxScalar`).
 #[derive(Clone, Debug, PartialEq)]
 pub struct BoxSpace<T: Copy + PartialOrd, const N: usize> {
     low: [T; N],
@@ -124,18 +359,14 @@ impl<T: Copy + PartialOrd, const N: usize> BoxSpace<T, N> {
     pub fn high(&self) -> &[T; N] { &self.high }
 }
 
-impl<T, const N: usize> Space for BoxSpace<T, N>
-where
-    T: Copy + PartialOrd + rand::distributions::uniform::SampleUniform,
-{
+impl<T: BoxScalar, const N: usize> Space for BoxSpace<T, N> {
     type Element = [T; N];
 
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
-        // Sample each dimension independently from Uniform[low, high]
+        // Sample each dimension independently (see `BoxScalar::sample_between`)
         let mut arr = self.low;
-        for i in 0..N {
-            let dist = Uniform::new_inclusive(self.low[i], self.high[i]);
-            arr[i] = dist.sample(rng);
+        for (i, v) in arr.iter_mut().enumerate() {
+            *v = T::sample_between(self.low[i], self.high[i], rng);
         }
         arr
     }
@@ -143,4 +374,8 @@ where
     fn contains(&self, elem: &Self::Element) -> bool {
         (0..N).all(|i| self.low[i] <= elem[i] && elem[i] <= self.high[i])
     }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        (0..N).try_for_each(|i| check_bounds(&format!("BoxSpace dim {i}"), elem[i], self.low[i], self.high[i]))
+    }
 }
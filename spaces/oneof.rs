@@ -0,0 +1,56 @@
+// OneOf spaces: tagged unions of subspaces (Gymnasium's `spaces.OneOf`).
+
+use rand::Rng;
+
+use super::{nested, Space};
+use crate::core::{GymError, Result};
+
+/// A choice of one subspace, e.g. parameterised actions where each action kind has its own
+/// argument space. Elements are `(index, value)` pairs with `value` in `spaces()[index]`.
+///
+/// All subspaces share one type `S`; subspaces of different kinds need an enum implementing
+/// `Space`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OneOf<S> {
+    spaces: Vec<S>,
+}
+
+impl<S: Space> OneOf<S> {
+    pub fn new<I: IntoIterator<Item = S>>(spaces: I) -> Self {
+        let spaces: Vec<S> = spaces.into_iter().collect();
+        assert!(!spaces.is_empty(), "OneOf requires at least one subspace");
+        Self { spaces }
+    }
+
+    /// The subspace of variant `index`.
+    pub fn get(&self, index: usize) -> Option<&S> { self.spaces.get(index) }
+
+    pub fn spaces(&self) -> &[S] { &self.spaces }
+    pub fn len(&self) -> usize { self.spaces.len() }
+    pub fn is_empty(&self) -> bool { self.spaces.is_empty() }
+
+    /// The subspace of variant `index`, or `GymError::OutOfSpace`.
+    pub(super) fn variant(&self, index: usize) -> Result<&S> {
+        self.spaces
+            .get(index)
+            .ok_or_else(|| GymError::OutOfSpace(format!("OneOf: variant {index} is outside [0, {})", self.spaces.len())))
+    }
+}
+
+impl<S: Space> Space for OneOf<S> {
+    type Element = (usize, S::Element);
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        // The variant is picked uniformly, then sampled from its subspace.
+        let index = rng.gen_range(0..self.spaces.len());
+        (index, self.spaces[index].sample(rng))
+    }
+
+    fn contains(&self, (index, value): &Self::Element) -> bool {
+        self.spaces.get(*index).is_some_and(|s| s.contains(value))
+    }
+
+    fn validate(&self, (index, value): &Self::Element) -> Result<()> {
+        self.variant(*index)?.validate(value).map_err(|e| nested(format_args!("OneOf variant {index}"), e))
+    }
+}
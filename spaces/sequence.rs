@@ -0,0 +1,64 @@
+// Sequence spaces: variable-length sequences over one space (Gymnasium's `spaces.Sequence`).
+
+use rand::Rng;
+
+use super::{nested, Space};
+use crate::core::{GymError, Result};
+
+/// Variable-length sequences of elements of `space`.
+///
+/// Unbounded sequences (`new`) sample their length from a geometric distribution with
+/// p = 0.25, as Gymnasium does; `bounded` sequences sample it uniformly from [min, max] and
+/// `contains` enforces the bounds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SequenceSpace<S> {
+    space: S,
+    min_length: usize,
+    max_length: Option<usize>,
+}
+
+impl<S: Space> SequenceSpace<S> {
+    pub fn new(space: S) -> Self { Self { space, min_length: 0, max_length: None } }
+
+    /// Sequences of `min_length..=max_length` elements.
+    pub fn bounded(space: S, min_length: usize, max_length: usize) -> Self {
+        assert!(min_length <= max_length, "SequenceSpace: min_length > max_length");
+        Self { space, min_length, max_length: Some(max_length) }
+    }
+
+    /// The space of each element.
+    pub fn space(&self) -> &S { &self.space }
+    pub fn min_length(&self) -> usize { self.min_length }
+    pub fn max_length(&self) -> Option<usize> { self.max_length }
+}
+
+impl<S: Space> Space for SequenceSpace<S> {
+    type Element = Vec<S::Element>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        let len = match self.max_length {
+            Some(max) => rng.gen_range(self.min_length..=max),
+            // Number of failures before the first success of a p = 0.25 Bernoulli trial
+            None => self.min_length + (0..).take_while(|_| !rng.gen_bool(0.25)).count(),
+        };
+        (0..len).map(|_| self.space.sample(rng)).collect()
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool {
+        elem.len() >= self.min_length
+            && self.max_length.is_none_or(|max| elem.len() <= max)
+            && elem.iter().all(|e| self.space.contains(e))
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        let len = elem.len();
+        if len < self.min_length || self.max_length.is_some_and(|max| len > max) {
+            let max = self.max_length.map_or("inf".to_string(), |max| max.to_string());
+            return Err(GymError::OutOfSpace(format!("SequenceSpace: length {len} is outside [{}, {max}]", self.min_length)));
+        }
+        for (i, e) in elem.iter().enumerate() {
+            self.space.validate(e).map_err(|err| nested(format_args!("SequenceSpace item {i}"), err))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// Box spaces whose shape is chosen at runtime, e.g. 84x84x3 images or kwarg-sized vectors.
+
+use rand::Rng;
+
+use super::{check_bounds, check_len, BoxScalar, BoxSpace, Space};
+use crate::core::{GymError, Result};
+
+/// A Box space with a runtime shape (any number of dimensions) and per-element inclusive bounds,
+/// which may be infinite for floats (see `BoxScalar`).
+/// Elements are flat row-major `Vec<T>`s of `shape.iter().product()` values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShapedBoxSpace<T: Copy + PartialOrd> {
+    shape: Vec<usize>,
+    low: Vec<T>,
+    high: Vec<T>,
+}
+
+impl<T: Copy + PartialOrd> ShapedBoxSpace<T> {
+    /// Per-element bounds, flattened row-major; both must hold `shape.iter().product()` values.
+    pub fn new<S: Into<Vec<usize>>>(shape: S, low: Vec<T>, high: Vec<T>) -> Self {
+        let shape = shape.into();
+        let len: usize = shape.iter().product();
+        assert!(low.len() == len && high.len() == len, "bounds must have {len} values for shape {shape:?}");
+        for i in 0..len {
+            assert!(low[i] <= high[i], "low[{i}] > high[{i}]");
+        }
+        Self { shape, low, high }
+    }
+
+    /// The same scalar bounds broadcast to every element, e.g. `(0, 255)` for `[84, 84, 3]` pixels.
+    pub fn uniform<S: Into<Vec<usize>>>(shape: S, low: T, high: T) -> Self {
+        let shape = shape.into();
+        let len = shape.iter().product();
+        Self::new(shape, vec![low; len], vec![high; len])
+    }
+
+    pub fn shape(&self) -> &[usize] { &self.shape }
+    pub fn low(&self) -> &[T] { &self.low }
+    pub fn high(&self) -> &[T] { &self.high }
+
+    /// Number of dimensions.
+    pub fn ndim(&self) -> usize { self.shape.len() }
+
+    /// Number of scalar values in an element.
+    pub fn len(&self) -> usize { self.low.len() }
+
+    pub fn is_empty(&self) -> bool { self.low.is_empty() }
+
+    /// The same bounds viewed with another shape holding as many values.
+    pub fn reshape<S: Into<Vec<usize>>>(mut self, shape: S) -> Self {
+        let shape = shape.into();
+        assert_eq!(shape.iter().product::<usize>(), self.len(), "cannot reshape {:?} into {shape:?}", self.shape);
+        self.shape = shape;
+        self
+    }
+}
+
+impl<T, const N: usize> From<BoxSpace<T, N>> for ShapedBoxSpace<T>
+where
+    T: Copy + PartialOrd,
+{
+    /// A 1-D space of shape `[N]` with the same bounds.
+    fn from(space: BoxSpace<T, N>) -> Self {
+        Self { shape: vec![N], low: space.low().to_vec(), high: space.high().to_vec() }
+    }
+}
+
+impl<T, const N: usize> TryFrom<ShapedBoxSpace<T>> for BoxSpace<T, N>
+where
+    T: Copy + PartialOrd,
+{
+    type Error = GymError;
+
+    /// Succeeds for 1-D spaces of shape `[N]`.
+    fn try_from(space: ShapedBoxSpace<T>) -> Result<Self> {
+        let err = GymError::NotSupported(format!("cannot convert shape {:?} into BoxSpace<_, {N}>", space.shape));
+        if space.shape != [N] {
+            return Err(err);
+        }
+        match (<[T; N]>::try_from(space.low), <[T; N]>::try_from(space.high)) {
+            (Ok(low), Ok(high)) => Ok(BoxSpace::new(low, high)),
+            _ => Err(err),
+        }
+    }
+}
+
+impl<T: BoxScalar> Space for ShapedBoxSpace<T> {
+    type Element = Vec<T>;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        // Sample each element independently (see `BoxScalar::sample_between`)
+        self.low.iter().zip(&self.high).map(|(&lo, &hi)| T::sample_between(lo, hi, rng)).collect()
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool {
+        elem.len() == self.len() && elem.iter().zip(self.low.iter().zip(&self.high)).all(|(v, (lo, hi))| lo <= v && v <= hi)
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        check_len("ShapedBoxSpace", elem.len(), self.len())?;
+        for (i, (&v, (&lo, &hi))) in elem.iter().zip(self.low.iter().zip(&self.high)).enumerate() {
+            check_bounds(&format!("ShapedBoxSpace index {i}"), v, lo, hi)?;
+        }
+        Ok(())
+    }
+}
@@ -2,6 +2,8 @@
 
 use rand::Rng;
 
+use crate::core::{GymError, Result};
+
 /// A trait implemented by all spaces.
 /// Element is the value type that lives in the space (e.g., u32 for Discrete,
 /// or [T; N] for a fixed-size BoxSpace).
@@ -13,4 +15,28 @@ pub trait Space {
 
     /// Return true if the given element is a valid member of the space.
     fn contains(&self, elem: &Self::Element) -> bool;
+
+    /// Like `contains`, but explain why an element is rejected with `GymError::OutOfSpace`,
+    /// naming the offending index, value and bounds (e.g. "BoxSpace dim 2: 3.7 > high 2.0").
+    /// Must return `Ok` exactly when `contains` returns true.
+    ///
+    /// The default implementation only reports that the element is outside the space.
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        if self.contains(elem) {
+            Ok(())
+        } else {
+            Err(GymError::OutOfSpace("element is outside the space".into()))
+        }
+    }
+}
+
+/// Structs whose fields are elements of spaces, together with the composite space they live in.
+///
+/// Usually implemented with `#[derive(Observation)]` (feature "derive"), which generates the
+/// space struct from the field types and their `#[space(...)]` bounds.
+pub trait Observation: Sized {
+    type Space: Space<Element = Self>;
+
+    /// The space of all values of this type.
+    fn space() -> Self::Space;
 }
@@ -0,0 +1,69 @@
+// Text spaces: strings over a character set (Gymnasium's `spaces.Text`).
+
+use rand::Rng;
+
+use super::Space;
+use crate::core::{GymError, Result};
+
+/// Strings of `min_length..=max_length` characters (counted as `char`s) drawn from a charset.
+/// The default charset is ASCII letters and digits, as in Gymnasium.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextSpace {
+    min_length: usize,
+    max_length: usize,
+    /// Sorted and deduplicated, so `contains` can binary-search it.
+    charset: Vec<char>,
+}
+
+impl TextSpace {
+    /// Alphanumeric strings with lengths in [min_length, max_length].
+    pub fn new(min_length: usize, max_length: usize) -> Self {
+        Self::with_charset(min_length, max_length, ('0'..='9').chain('A'..='Z').chain('a'..='z'))
+    }
+
+    /// Strings over a custom charset.
+    pub fn with_charset<I: IntoIterator<Item = char>>(min_length: usize, max_length: usize, charset: I) -> Self {
+        assert!(min_length <= max_length, "TextSpace: min_length > max_length");
+        let mut charset: Vec<char> = charset.into_iter().collect();
+        charset.sort_unstable();
+        charset.dedup();
+        assert!(!charset.is_empty() || max_length == 0, "TextSpace requires a non-empty charset");
+        Self { min_length, max_length, charset }
+    }
+
+    pub fn min_length(&self) -> usize { self.min_length }
+    pub fn max_length(&self) -> usize { self.max_length }
+    pub fn charset(&self) -> &[char] { &self.charset }
+
+    /// Position of `c` in the sorted charset.
+    pub fn char_index(&self, c: char) -> Option<usize> { self.charset.binary_search(&c).ok() }
+}
+
+impl Space for TextSpace {
+    type Element = String;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+        // Uniform length, then uniform characters
+        let len = rng.gen_range(self.min_length..=self.max_length);
+        (0..len).map(|_| self.charset[rng.gen_range(0..self.charset.len())]).collect()
+    }
+
+    fn contains(&self, elem: &Self::Element) -> bool {
+        let len = elem.chars().count();
+        (self.min_length..=self.max_length).contains(&len) && elem.chars().all(|c| self.char_index(c).is_some())
+    }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> {
+        let len = elem.chars().count();
+        if !(self.min_length..=self.max_length).contains(&len) {
+            return Err(GymError::OutOfSpace(format!(
+                "TextSpace: length {len} is outside [{}, {}]",
+                self.min_length, self.max_length
+            )));
+        }
+        match elem.chars().enumerate().find(|&(_, c)| self.char_index(c).is_none()) {
+            Some((i, c)) => Err(GymError::OutOfSpace(format!("TextSpace char {i}: {c:?} is not in the charset"))),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,62 @@
+// Tuple spaces: heterogeneous products of spaces (Gymnasium's `spaces.Tuple`).
+
+use rand::Rng;
+
+use super::{nested, Space};
+use crate::core::Result;
+
+/// A product of heterogeneous spaces, e.g. `(image, vector)` observations or
+/// `(discrete mode, continuous parameter)` actions.
+/// Elements are tuples of the component elements; tuples of spaces nest freely.
+///
+/// Plain tuples of spaces (up to 8 components) are spaces themselves; `TupleSpace` names them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TupleSpace<S> {
+    spaces: S,
+}
+
+impl<S: Space> TupleSpace<S> {
+    pub fn new(spaces: S) -> Self { Self { spaces } }
+
+    pub fn spaces(&self) -> &S { &self.spaces }
+    pub fn into_inner(self) -> S { self.spaces }
+}
+
+impl<S: Space> Space for TupleSpace<S> {
+    type Element = S::Element;
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element { self.spaces.sample(rng) }
+
+    fn contains(&self, elem: &Self::Element) -> bool { self.spaces.contains(elem) }
+
+    fn validate(&self, elem: &Self::Element) -> Result<()> { self.spaces.validate(elem) }
+}
+
+macro_rules! tuple_space {
+    ($($S:ident $i:tt),+) => {
+        impl<$($S: Space),+> Space for ($($S,)+) {
+            type Element = ($($S::Element,)+);
+
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element {
+                // Components are sampled in order, so a seeded RNG yields a reproducible tuple.
+                ($(self.$i.sample(rng),)+)
+            }
+
+            fn contains(&self, elem: &Self::Element) -> bool { $(self.$i.contains(&elem.$i))&&+ }
+
+            fn validate(&self, elem: &Self::Element) -> Result<()> {
+                $(self.$i.validate(&elem.$i).map_err(|e| nested(concat!("tuple component ", $i), e))?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+tuple_space!(A 0);
+tuple_space!(A 0, B 1);
+tuple_space!(A 0, B 1, C 2);
+tuple_space!(A 0, B 1, C 2, D 3);
+tuple_space!(A 0, B 1, C 2, D 3, E 4);
+tuple_space!(A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_space!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_space!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
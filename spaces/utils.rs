@@ -0,0 +1,318 @@
+// Flattening utilities (Gymnasium's `spaces.utils`): fixed-length `Vec<f32>` views of elements,
+// e.g. as neural network inputs.
+
+use std::collections::BTreeMap;
+
+use super::{
+    BitVector, BoxScalar, BoxSpace, DictSpace, Discrete, MultiBinary, MultiDiscrete, OneOf, PackedMultiBinary,
+    ShapedBoxSpace, Space, TextSpace, TupleSpace,
+};
+use crate::core::{GymError, Result};
+
+/// Spaces whose elements flatten to a fixed-length `Vec<f32>`:
+/// - `Discrete`: one-hot of length n (index `elem - start`)
+/// - `MultiDiscrete`: concatenated one-hots, one per dimension
+/// - `OneOf`: the variant index followed by the variant's flattening, zero-padded to the longest variant
+/// - `MultiBinary`, `PackedMultiBinary`, `BoxSpace`, `ShapedBoxSpace`: the values as f32
+/// - `TextSpace`: `max_length` charset indices, padded with `charset().len()`
+/// - tuples, `TupleSpace` and `DictSpace`: concatenation of the components (dicts in key order)
+///
+/// Variable-size spaces (`SequenceSpace`, `GraphSpace`) have no fixed-length encoding.
+pub trait Flatten: Space {
+    /// Length of flattened elements.
+    fn flatdim(&self) -> usize;
+
+    /// Flatten an element of this space.
+    fn flatten(&self, elem: &Self::Element) -> Vec<f32>;
+
+    /// Rebuild an element from `flatten` output.
+    /// Returns `GymError::InvalidObservation` if `flat` has the wrong length or is not a valid encoding.
+    fn unflatten(&self, flat: &[f32]) -> Result<Self::Element>;
+
+    /// The Box space flattened elements live in.
+    fn flatten_space(&self) -> ShapedBoxSpace<f32>;
+}
+
+/// See `Flatten::flatdim`.
+pub fn flatdim<S: Flatten>(space: &S) -> usize { space.flatdim() }
+
+/// See `Flatten::flatten`.
+pub fn flatten<S: Flatten>(space: &S, elem: &S::Element) -> Vec<f32> { space.flatten(elem) }
+
+/// See `Flatten::unflatten`.
+pub fn unflatten<S: Flatten>(space: &S, flat: &[f32]) -> Result<S::Element> { space.unflatten(flat) }
+
+/// See `Flatten::flatten_space`.
+pub fn flatten_space<S: Flatten>(space: &S) -> ShapedBoxSpace<f32> { space.flatten_space() }
+
+/// Return `GymError::InvalidObservation` unless `flat` holds exactly `dim` values.
+pub(crate) fn check_flat_len(flat: &[f32], dim: usize, space: &str) -> Result<()> {
+    if flat.len() == dim {
+        Ok(())
+    } else {
+        Err(GymError::InvalidObservation(format!("{space} flattens to {dim} values, got {}", flat.len())))
+    }
+}
+
+/// Split `flat` into consecutive chunks of the given lengths.
+pub(crate) fn split<'a>(mut flat: &'a [f32], dims: &[usize]) -> Vec<&'a [f32]> {
+    dims.iter()
+        .map(|&d| {
+            let (head, rest) = flat.split_at(d);
+            flat = rest;
+            head
+        })
+        .collect()
+}
+
+fn one_hot(i: u32, n: u32) -> impl Iterator<Item = f32> { (0..n).map(move |j| if j == i { 1.0 } else { 0.0 }) }
+
+fn from_one_hot(flat: &[f32], space: &str) -> Result<u32> {
+    let mut hot = flat.iter().enumerate().filter(|(_, v)| **v != 0.0);
+    match (hot.next(), hot.next()) {
+        (Some((i, 1.0)), None) => Ok(i as u32),
+        _ => Err(GymError::InvalidObservation(format!("{space}: {flat:?} is not a one-hot vector"))),
+    }
+}
+
+impl Flatten for Discrete {
+    fn flatdim(&self) -> usize { self.n() as usize }
+
+    fn flatten(&self, elem: &u32) -> Vec<f32> { one_hot(elem.wrapping_sub(self.start()), self.n()).collect() }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<u32> {
+        check_flat_len(flat, self.flatdim(), "Discrete")?;
+        Ok(self.start() + from_one_hot(flat, "Discrete")?)
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> { ShapedBoxSpace::uniform([self.flatdim()], 0.0, 1.0) }
+}
+
+impl Flatten for MultiDiscrete {
+    fn flatdim(&self) -> usize { self.nvec().iter().map(|&n| n as usize).sum() }
+
+    fn flatten(&self, elem: &Vec<u32>) -> Vec<f32> {
+        elem.iter().zip(self.nvec().iter().zip(self.start())).flat_map(|(&v, (&n, &s))| one_hot(v.wrapping_sub(s), n)).collect()
+    }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<Vec<u32>> {
+        check_flat_len(flat, self.flatdim(), "MultiDiscrete")?;
+        let dims: Vec<usize> = self.nvec().iter().map(|&n| n as usize).collect();
+        split(flat, &dims)
+            .into_iter()
+            .zip(self.start())
+            .map(|(chunk, &s)| Ok(s + from_one_hot(chunk, "MultiDiscrete")?))
+            .collect()
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> { ShapedBoxSpace::uniform([self.flatdim()], 0.0, 1.0) }
+}
+
+impl Flatten for MultiBinary {
+    fn flatdim(&self) -> usize { self.n() }
+
+    fn flatten(&self, elem: &Vec<u8>) -> Vec<f32> { elem.iter().map(|&b| f32::from(b)).collect() }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<Vec<u8>> {
+        check_flat_len(flat, self.flatdim(), "MultiBinary")?;
+        Ok(flat.iter().map(|&v| u8::from(v != 0.0)).collect())
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> { ShapedBoxSpace::uniform([self.n()], 0.0, 1.0) }
+}
+
+impl Flatten for PackedMultiBinary {
+    fn flatdim(&self) -> usize { self.n() }
+
+    fn flatten(&self, elem: &BitVector) -> Vec<f32> { elem.iter().map(f32::from).collect() }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<BitVector> {
+        check_flat_len(flat, self.flatdim(), "PackedMultiBinary")?;
+        Ok(flat.iter().map(|&v| v != 0.0).collect())
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> { ShapedBoxSpace::uniform([self.n()], 0.0, 1.0) }
+}
+
+impl<T: BoxScalar, const N: usize> Flatten for BoxSpace<T, N> {
+    fn flatdim(&self) -> usize { N }
+
+    fn flatten(&self, elem: &[T; N]) -> Vec<f32> { elem.iter().map(|&v| v.to_f32()).collect() }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<[T; N]> {
+        check_flat_len(flat, N, "BoxSpace")?;
+        Ok(std::array::from_fn(|i| T::from_f32(flat[i])))
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> {
+        ShapedBoxSpace::new([N], self.low().map(T::to_f32).to_vec(), self.high().map(T::to_f32).to_vec())
+    }
+}
+
+impl<T: BoxScalar> Flatten for ShapedBoxSpace<T> {
+    fn flatdim(&self) -> usize { self.len() }
+
+    fn flatten(&self, elem: &Vec<T>) -> Vec<f32> { elem.iter().map(|&v| v.to_f32()).collect() }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<Vec<T>> {
+        check_flat_len(flat, self.len(), "ShapedBoxSpace")?;
+        Ok(flat.iter().map(|&v| T::from_f32(v)).collect())
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> {
+        let convert = |b: &[T]| b.iter().map(|&v| v.to_f32()).collect();
+        ShapedBoxSpace::new([self.len()], convert(self.low()), convert(self.high()))
+    }
+}
+
+impl<S: Flatten> Flatten for TupleSpace<S> {
+    fn flatdim(&self) -> usize { self.spaces().flatdim() }
+    fn flatten(&self, elem: &S::Element) -> Vec<f32> { self.spaces().flatten(elem) }
+    fn unflatten(&self, flat: &[f32]) -> Result<S::Element> { self.spaces().unflatten(flat) }
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> { self.spaces().flatten_space() }
+}
+
+/// Concatenate the bounds of flattened component spaces.
+pub(crate) fn concat_spaces(spaces: &[ShapedBoxSpace<f32>]) -> ShapedBoxSpace<f32> {
+    let low: Vec<f32> = spaces.iter().flat_map(|s| s.low().iter().copied()).collect();
+    let high: Vec<f32> = spaces.iter().flat_map(|s| s.high().iter().copied()).collect();
+    ShapedBoxSpace::new([low.len()], low, high)
+}
+
+macro_rules! flatten_tuple {
+    ($($S:ident $i:tt),+) => {
+        impl<$($S: Flatten),+> Flatten for ($($S,)+) {
+            fn flatdim(&self) -> usize { 0 $(+ self.$i.flatdim())+ }
+
+            fn flatten(&self, elem: &Self::Element) -> Vec<f32> {
+                let mut out = Vec::with_capacity(self.flatdim());
+                $(out.extend(self.$i.flatten(&elem.$i));)+
+                out
+            }
+
+            fn unflatten(&self, flat: &[f32]) -> Result<Self::Element> {
+                check_flat_len(flat, self.flatdim(), "tuple space")?;
+                let chunks = split(flat, &[$(self.$i.flatdim()),+]);
+                Ok(($(self.$i.unflatten(chunks[$i])?,)+))
+            }
+
+            fn flatten_space(&self) -> ShapedBoxSpace<f32> { concat_spaces(&[$(self.$i.flatten_space()),+]) }
+        }
+    };
+}
+
+flatten_tuple!(A 0);
+flatten_tuple!(A 0, B 1);
+flatten_tuple!(A 0, B 1, C 2);
+flatten_tuple!(A 0, B 1, C 2, D 3);
+flatten_tuple!(A 0, B 1, C 2, D 3, E 4);
+flatten_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+flatten_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+flatten_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<S: Flatten> Flatten for DictSpace<S> {
+    fn flatdim(&self) -> usize { self.iter().map(|(_, s)| s.flatdim()).sum() }
+
+    /// Panics if a key of the space is missing from `elem`.
+    fn flatten(&self, elem: &BTreeMap<String, S::Element>) -> Vec<f32> {
+        self.iter()
+            .flat_map(|(k, s)| s.flatten(elem.get(k).unwrap_or_else(|| panic!("DictSpace element lacks key {k:?}"))))
+            .collect()
+    }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<BTreeMap<String, S::Element>> {
+        check_flat_len(flat, self.flatdim(), "DictSpace")?;
+        let dims: Vec<usize> = self.iter().map(|(_, s)| s.flatdim()).collect();
+        self.iter().zip(split(flat, &dims)).map(|((k, s), chunk)| Ok((k.to_string(), s.unflatten(chunk)?))).collect()
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> {
+        concat_spaces(&self.iter().map(|(_, s)| s.flatten_space()).collect::<Vec<_>>())
+    }
+}
+
+impl Flatten for TextSpace {
+    fn flatdim(&self) -> usize { self.max_length() }
+
+    fn flatten(&self, elem: &String) -> Vec<f32> {
+        let pad = self.charset().len() as f32;
+        let mut out: Vec<f32> = elem.chars().map(|c| self.char_index(c).map_or(pad, |i| i as f32)).collect();
+        out.resize(self.max_length(), pad);
+        out
+    }
+
+    /// Rejects values that are not charset indices or the pad value, characters after the first
+    /// pad value, and strings shorter than `min_length`.
+    fn unflatten(&self, flat: &[f32]) -> Result<String> {
+        check_flat_len(flat, self.max_length(), "TextSpace")?;
+        let pad = self.charset().len();
+        let indices = flat
+            .iter()
+            .map(|&v| {
+                if v.fract() == 0.0 && (0.0..=pad as f32).contains(&v) {
+                    Ok(v as usize)
+                } else {
+                    Err(GymError::InvalidObservation(format!("TextSpace: {v} is not a charset index or the pad value {pad}")))
+                }
+            })
+            .collect::<Result<Vec<usize>>>()?;
+        let len = indices.iter().position(|&i| i == pad).unwrap_or(indices.len());
+        if let Some(i) = indices[len..].iter().position(|&i| i != pad) {
+            return Err(GymError::InvalidObservation(format!("TextSpace: character at {} follows the padding", len + i)));
+        }
+        if len < self.min_length() {
+            return Err(GymError::InvalidObservation(format!(
+                "TextSpace: {len} characters, fewer than min_length {}",
+                self.min_length()
+            )));
+        }
+        Ok(indices[..len].iter().map(|&i| self.charset()[i]).collect())
+    }
+
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> {
+        ShapedBoxSpace::uniform([self.max_length()], 0.0, self.charset().len() as f32)
+    }
+}
+
+impl<S: Flatten> Flatten for OneOf<S> {
+    fn flatdim(&self) -> usize { 1 + self.spaces().iter().map(Flatten::flatdim).max().unwrap_or(0) }
+
+    /// Panics if the variant index is out of range.
+    fn flatten(&self, (index, value): &(usize, S::Element)) -> Vec<f32> {
+        let space = self.get(*index).unwrap_or_else(|| panic!("OneOf has no variant {index}"));
+        let mut out = Vec::with_capacity(self.flatdim());
+        out.push(*index as f32);
+        out.extend(space.flatten(value));
+        out.resize(self.flatdim(), 0.0);
+        out
+    }
+
+    fn unflatten(&self, flat: &[f32]) -> Result<(usize, S::Element)> {
+        check_flat_len(flat, self.flatdim(), "OneOf")?;
+        let index = flat[0] as usize;
+        let space = self
+            .get(index)
+            .filter(|_| flat[0] == index as f32)
+            .ok_or_else(|| GymError::InvalidObservation(format!("OneOf: {} is not a variant index", flat[0])))?;
+        let (body, padding) = flat[1..].split_at(space.flatdim());
+        if padding.iter().any(|&v| v != 0.0) {
+            return Err(GymError::InvalidObservation(format!("OneOf: variant {index} has non-zero padding")));
+        }
+        Ok((index, space.unflatten(body)?))
+    }
+
+    /// Each position is bounded by the union of the variants' bounds there, with 0 for padding.
+    fn flatten_space(&self) -> ShapedBoxSpace<f32> {
+        let dim = self.flatdim();
+        let mut low = vec![0.0f32; dim];
+        let mut high = vec![(self.len() - 1) as f32; dim];
+        high[1..].fill(0.0);
+        for space in self.spaces().iter().map(Flatten::flatten_space) {
+            for (i, (&l, &h)) in space.low().iter().zip(space.high()).enumerate() {
+                low[i + 1] = low[i + 1].min(l);
+                high[i + 1] = high[i + 1].max(h);
+            }
+        }
+        ShapedBoxSpace::new([dim], low, high)
+    }
+}
@@ -6,15 +6,54 @@ pub mod wrappers;
 pub mod vector;
 pub mod registry;
 
-pub use crate::core::{Env, GymError, Info, InfoValue, RenderFrame, Result, Step};
-pub use crate::spaces::{BoxSpace, Discrete, MultiBinary, MultiDiscrete, Space};
-pub use crate::envs::{CartPoleEnv, MountainCarEnv, MountainCarContinuousEnv, AcrobotEnv, PendulumEnv, LunarLanderEnv};
-pub use crate::wrappers::{TimeLimit, ClipAction, ClipReward, TransformObservation, TransformAction, TransformReward, RecordEpisodeStatistics};
-pub use crate::utils::{encode_png, save_png};
+pub use crate::core::{Env, EnvMetadata, GymError, Info, InfoValue, RenderFrame, RenderMode, ResetOptions, Result, Reward, RewardScalar, StatefulEnv, Step};
+pub use crate::spaces::{BoxSpace, Discrete, MultiBinary, MultiDiscrete, Space, Observation, TupleSpace, DictSpace, ShapedBoxSpace, OneOf, DynSpace, DynValue, ActionMask, ACTION_MASK_KEY};
+pub use crate::envs::{CartPoleEnv, MountainCarEnv, MountainCarContinuousEnv, AcrobotEnv, PendulumEnv, LunarLanderEnv, MoLunarLanderEnv};
+pub use crate::wrappers::{TimeLimit, ClipAction, ClipReward, TransformObservation, TransformAction, TransformReward, LinearReward, RecordEpisodeStatistics, OrderEnforcing, PassiveEnvChecker};
+pub use crate::utils::{check_env, encode_png, save_png};
 pub use crate::vector::SyncVectorEnv;
 
 // Registration API exports (README Step 8)
-pub use crate::registry::{EnvSpec, EnvDyn, KwArgs, register, get_spec, make, factory_of};
+pub use crate::registry::{EnvSpec, EnvDyn, KwArgs, register, get_spec, make, factory_of, factory_of_stateful};
+
+#[cfg(feature = "derive")]
+pub use rust_gymnasium_derive::Observation;
+
+// Support for the code generated by `#[derive(Observation)]`; not a public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::collections::BTreeMap;
+
+    use crate::spaces::{utils, DynValue, ShapedBoxSpace};
+    use crate::GymError;
+
+    pub use rand::Rng;
+
+    /// Prefix the error of a field's space with `Struct.field`.
+    pub fn nested(what: &str, error: GymError) -> GymError { crate::spaces::nested(what, error) }
+
+    /// Split `flat` into consecutive chunks of the given lengths, which must add up to its length.
+    pub fn split_flat<'a>(space: &str, flat: &'a [f32], dims: &[usize]) -> crate::Result<Vec<&'a [f32]>> {
+        utils::check_flat_len(flat, dims.iter().sum(), space)?;
+        Ok(utils::split(flat, dims))
+    }
+
+    pub fn concat_spaces(spaces: &[ShapedBoxSpace<f32>]) -> ShapedBoxSpace<f32> { utils::concat_spaces(spaces) }
+
+    /// The field values of a `DynValue::Dict`, which must have exactly the given keys.
+    pub fn dyn_fields(space: &str, value: DynValue, keys: &[&str]) -> crate::Result<BTreeMap<String, DynValue>> {
+        let DynValue::Dict(fields) = value else {
+            return Err(GymError::OutOfSpace(format!("{space} space cannot hold a {} value", value.kind())));
+        };
+        if let Some(key) = keys.iter().find(|k| !fields.contains_key(**k)) {
+            return Err(GymError::OutOfSpace(format!("{space}: missing field {key:?}")));
+        }
+        if let Some(key) = fields.keys().find(|k| !keys.contains(&k.as_str())) {
+            return Err(GymError::OutOfSpace(format!("{space}: unexpected field {key:?}")));
+        }
+        Ok(fields)
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -24,12 +63,18 @@ mod tests {
 
     /// A tiny dummy environment to validate the trait compiles and basic methods work.
     struct CounterEnv {
-        state: i32,
+        state: u32,
     }
 
     impl Env for CounterEnv {
-        type Obs = i32;
-        type Act = i32;
+        type Obs = u32;
+        type Act = u32;
+        type ObsSpace = Discrete;
+        type ActSpace = Discrete;
+        type Reward = f32;
+
+        fn observation_space(&self) -> Self::ObsSpace { Discrete::new(8) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(3) }
 
         fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) {
             self.state = 0;
@@ -106,6 +151,496 @@ mod tests {
         assert!(!md.contains(&bad_md));
     }
 
+    #[test]
+    fn spaces_tuple_nesting() {
+        let mut rng = StdRng::seed_from_u64(9);
+        // (discrete mode, (continuous parameter, binary flags))
+        let space = TupleSpace::new((Discrete::new(3), (BoxSpace::new([-1.0f32], [1.0]), MultiBinary::new(2))));
+        for _ in 0..50 {
+            let v = space.sample(&mut rng);
+            assert!(space.contains(&v));
+            assert!(v.0 < 3 && (-1.0..=1.0).contains(&v.1.0[0]));
+        }
+        assert!(!space.contains(&(3, ([0.0], vec![0, 1]))));
+        assert!(!space.contains(&(0, ([2.0], vec![0, 1]))));
+        assert!(!space.contains(&(0, ([0.0], vec![0, 1, 1]))));
+
+        let (mut a, mut b) = (StdRng::seed_from_u64(1), StdRng::seed_from_u64(1));
+        assert_eq!(space.sample(&mut a), space.spaces().sample(&mut b));
+    }
+
+    #[test]
+    fn spaces_dict_sampling_and_filter() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let goal = BoxSpace::new([-1.0f32; 3], [1.0; 3]);
+        let space = DictSpace::new([
+            ("observation", BoxSpace::new([-10.0f32; 3], [10.0; 3])),
+            ("achieved_goal", goal.clone()),
+            ("desired_goal", goal),
+        ]);
+        assert_eq!(space.keys().collect::<Vec<_>>(), ["achieved_goal", "desired_goal", "observation"]);
+        let mut v = space.sample(&mut rng);
+        assert!(space.contains(&v));
+
+        let goals = space.filter(["desired_goal", "achieved_goal"]);
+        assert_eq!(goals.len(), 2);
+        let g = goals.filter_element(&v);
+        assert!(goals.contains(&g) && !goals.contains(&v));
+
+        v.get_mut("desired_goal").unwrap()[0] = 5.0;
+        assert!(!space.contains(&v));
+        v.remove("desired_goal");
+        assert!(!space.contains(&v));
+    }
+
+    #[test]
+    fn spaces_shaped_box() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let pixels = ShapedBoxSpace::uniform([84, 84, 3], 0u8, 255);
+        assert_eq!((pixels.ndim(), pixels.len()), (3, 84 * 84 * 3));
+        let img = pixels.sample(&mut rng);
+        assert!(pixels.contains(&img));
+        assert!(!pixels.contains(&img[1..].to_vec()));
+
+        let b = ShapedBoxSpace::new([2, 2], vec![0.0, -1.0, 0.0, 0.0], vec![1.0, 1.0, 0.5, 0.0]);
+        for _ in 0..50 {
+            assert!(b.contains(&b.sample(&mut rng)));
+        }
+        assert!(!b.contains(&vec![0.0, 0.0, 0.6, 0.0]));
+
+        let fixed = BoxSpace::new([0.0f32, -1.0, 2.5], [1.0, 1.0, 3.5]);
+        let shaped = ShapedBoxSpace::from(fixed.clone());
+        assert_eq!(shaped.shape(), [3]);
+        assert_eq!(BoxSpace::<f32, 3>::try_from(shaped.clone()).ok(), Some(fixed));
+        assert!(BoxSpace::<f32, 3>::try_from(shaped.reshape([3, 1])).is_err());
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn shaped_box_ndarray_roundtrip() {
+        let space = ShapedBoxSpace::uniform([2, 3], 0i32, 10);
+        let arr = space.to_ndarray(vec![1, 2, 3, 4, 5, 6]).expect("shape matches");
+        assert_eq!(arr.shape(), [2, 3]);
+        assert_eq!(arr[[1, 0]], 4);
+        assert_eq!(space.from_ndarray(&arr).expect("shape matches"), vec![1, 2, 3, 4, 5, 6]);
+        assert!(space.from_ndarray(&arr.t().to_owned().into_dyn()).is_err());
+    }
+
+    #[test]
+    fn cartpole_velocities_are_unbounded() {
+        let space = CartPoleEnv::default().observation_space();
+        assert_eq!(space.high()[1], f32::INFINITY);
+        assert_eq!(space.low()[3], f32::NEG_INFINITY);
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let v = space.sample(&mut rng);
+            assert!(space.contains(&v) && v.iter().all(|x| x.is_finite()));
+        }
+
+        let half = ShapedBoxSpace::new([2], vec![0.0f32, f32::NEG_INFINITY], vec![f32::INFINITY, 0.0]);
+        let v = half.sample(&mut rng);
+        assert!(v[0] >= 0.0 && v[1] <= 0.0);
+    }
+
+    #[test]
+    fn spaces_masked_sampling() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let d = Discrete::new(5);
+        let mask = ActionMask::from_fn(5, |i| i % 2 == 1);
+        let mut seen = [false; 5];
+        for _ in 0..200 {
+            let a = d.sample_masked(&mut rng, &mask).expect("some action is valid");
+            assert!(mask.is_allowed(a as usize));
+            seen[a as usize] = true;
+        }
+        assert_eq!(seen, [false, true, false, true, false]);
+        assert_eq!(d.sample_masked(&mut rng, &ActionMask::new([false; 5])), None);
+
+        let md = MultiDiscrete::new(vec![2, 3]);
+        let masks = [ActionMask::all(2), ActionMask::new([false, false, true])];
+        for _ in 0..20 {
+            let v = md.sample_masked(&mut rng, &masks).expect("valid");
+            assert!(md.contains(&v) && v[1] == 2);
+        }
+
+        let mut info = Info::new();
+        mask.insert_into(&mut info);
+        assert_eq!(info.get_bytes(ACTION_MASK_KEY), Some(&[0, 1, 0, 1, 0][..]));
+        assert_eq!(ActionMask::from_info(&info), Some(mask));
+    }
+
+    #[test]
+    fn spaces_flatten_roundtrip() {
+        use crate::spaces::{flatdim, flatten, flatten_space, unflatten};
+        let mut rng = StdRng::seed_from_u64(2);
+
+        let d = Discrete::new(4);
+        assert_eq!(flatten(&d, &2), vec![0.0, 0.0, 1.0, 0.0]);
+        assert_eq!(unflatten(&d, &[0.0, 1.0, 0.0, 0.0]).ok(), Some(1));
+        assert!(matches!(unflatten(&d, &[0.0, 1.0, 1.0, 0.0]), Err(GymError::InvalidObservation(_))));
+
+        let md = MultiDiscrete::new(vec![2, 3]);
+        assert_eq!(flatten(&md, &vec![1, 0]), vec![0.0, 1.0, 1.0, 0.0, 0.0]);
+        assert_eq!(flatten_space(&md).shape(), [5]);
+
+        let obs = DictSpace::new([
+            ("pos", (BoxSpace::new([-1.0f32, 0.0], [1.0, f32::INFINITY]), MultiBinary::new(3))),
+            ("cell", (BoxSpace::new([0.0f32, 0.0], [9.0, 9.0]), MultiBinary::new(3))),
+        ]);
+        let space = TupleSpace::new((Discrete::new(3), obs, ShapedBoxSpace::uniform([2, 2], 0u8, 255)));
+        assert_eq!(flatdim(&space), 3 + 2 * (2 + 3) + 4);
+        let flat_space = flatten_space(&space);
+        assert_eq!(flat_space.len(), flatdim(&space));
+        // Dict components are flattened in key order: "cell" before "pos".
+        assert_eq!(flat_space.high()[3..6], [9.0, 9.0, 1.0]);
+        for _ in 0..20 {
+            let v = space.sample(&mut rng);
+            let flat = flatten(&space, &v);
+            assert!(flat_space.contains(&flat));
+            assert_eq!(unflatten(&space, &flat).expect("valid encoding"), v);
+        }
+        assert!(unflatten(&space, &[0.0; 3]).is_err());
+    }
+
+    #[test]
+    fn spaces_text_sequence_graph() {
+        use crate::spaces::{Flatten, GraphInstance, GraphSpace, SequenceSpace, TextSpace};
+        use crate::utils::rng_from_seed;
+        let (mut a, mut b) = (rng_from_seed(8), rng_from_seed(8));
+
+        let text = TextSpace::with_charset(2, 6, "abc".chars());
+        for _ in 0..50 {
+            let s = text.sample(&mut a);
+            assert!(text.contains(&s) && (2..=6).contains(&s.len()));
+            assert_eq!(s, text.sample(&mut b));
+            assert_eq!(text.unflatten(&text.flatten(&s)).ok(), Some(s));
+        }
+        assert!(!text.contains(&"abd".to_string()) && !text.contains(&"a".to_string()));
+        assert!(TextSpace::new(0, 8).contains(&"Go2".to_string()));
+        // Fractional or negative indices, characters after the padding and short strings
+        let invalid = [
+            [0.5, 1.0, 3.0, 3.0, 3.0, 3.0],
+            [-1.0, 1.0, 3.0, 3.0, 3.0, 3.0],
+            [0.0, 1.0, 3.0, 2.0, 3.0, 3.0],
+            [0.0, 3.0, 3.0, 3.0, 3.0, 3.0],
+        ];
+        for flat in invalid {
+            assert!(matches!(text.unflatten(&flat), Err(GymError::InvalidObservation(_))), "{flat:?}");
+        }
+        assert_eq!(text.unflatten(&[2.0, 0.0, 3.0, 3.0, 3.0, 3.0]).unwrap(), "ca");
+
+        let seq = SequenceSpace::new(Discrete::new(3));
+        let bounded = SequenceSpace::bounded(BoxSpace::new([0.0f32], [1.0]), 1, 3);
+        for _ in 0..50 {
+            let v = seq.sample(&mut a);
+            assert!(seq.contains(&v));
+            assert_eq!(v, seq.sample(&mut b));
+            let w = bounded.sample(&mut a);
+            assert!(bounded.contains(&w) && (1..=3).contains(&w.len()));
+            bounded.sample(&mut b);
+        }
+        assert!(!seq.contains(&vec![0, 3]));
+        assert!(!bounded.contains(&vec![]));
+
+        let graph = GraphSpace::new(BoxSpace::new([0.0f32; 2], [1.0; 2]), Some(Discrete::new(4))).with_max_nodes(5);
+        for _ in 0..20 {
+            let g = graph.sample(&mut a);
+            assert!(graph.contains(&g) && (1..=5).contains(&g.nodes.len()));
+            assert_eq!(g, graph.sample(&mut b));
+        }
+        let g = GraphInstance { nodes: vec![[0.5, 0.5]; 2], edges: Some(vec![1]), edge_links: vec![[0, 2]] };
+        assert!(!graph.contains(&g));
+        let nodes_only: GraphSpace<Discrete, Discrete> = GraphSpace::new(Discrete::new(2), None);
+        assert!(nodes_only.contains(&GraphInstance { nodes: vec![1, 0], edges: None, edge_links: vec![[1, 0]] }));
+    }
+
+    #[test]
+    fn spaces_discrete_start_and_oneof() {
+        use crate::spaces::Flatten;
+        use crate::utils::rng_from_seed;
+        let mut rng = rng_from_seed(9);
+
+        let d = Discrete::with_start(3, 5);
+        for _ in 0..50 {
+            let v = d.sample(&mut rng);
+            assert!((5..8).contains(&v) && d.contains(&v));
+            assert_eq!(d.unflatten(&d.flatten(&v)).ok(), Some(v));
+        }
+        assert!(!d.contains(&4) && !d.contains(&8));
+        let mask = ActionMask::new(vec![false, false, true]);
+        assert_eq!(d.sample_masked(&mut rng, &mask), Some(7));
+
+        let md = MultiDiscrete::with_start([2, 3], [1, 10]);
+        for _ in 0..50 {
+            let v = md.sample(&mut rng);
+            assert!(md.contains(&v) && (1..3).contains(&v[0]) && (10..13).contains(&v[1]));
+            assert_eq!(md.unflatten(&md.flatten(&v)).ok(), Some(v));
+        }
+        assert!(!md.contains(&vec![0, 10]) && !md.contains(&vec![1, 13]));
+
+        let one_of = OneOf::new([Discrete::new(2), Discrete::with_start(4, 3)]);
+        let flat_space = one_of.flatten_space();
+        let mut seen = [false; 2];
+        for _ in 0..50 {
+            let e = one_of.sample(&mut rng);
+            assert!(one_of.contains(&e));
+            seen[e.0] = true;
+            let flat = one_of.flatten(&e);
+            assert!(flat_space.contains(&flat));
+            assert_eq!(one_of.unflatten(&flat).ok(), Some(e));
+        }
+        assert_eq!(seen, [true, true]);
+        assert!(!one_of.contains(&(0, 2)) && one_of.contains(&(1, 6)) && !one_of.contains(&(2, 0)));
+        assert!(one_of.unflatten(&[0.0, 1.0, 0.0, 1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn spaces_validate_describes_errors() {
+        use std::collections::BTreeMap;
+        let msg = |r: Result<()>| r.unwrap_err().to_string();
+
+        let b = BoxSpace::new([0.0f32; 3], [2.0; 3]);
+        assert!(b.validate(&[1.0, 2.0, 0.0]).is_ok());
+        assert_eq!(msg(b.validate(&[1.0, 2.0, 3.7])), "BoxSpace dim 2: 3.7 > high 2.0");
+        assert_eq!(msg(b.validate(&[-1.0, 0.0, 0.0])), "BoxSpace dim 0: -1.0 < low 0.0");
+        assert_eq!(msg(b.validate(&[0.0, f32::NAN, 0.0])), "BoxSpace dim 1: NaN is not within [0.0, 2.0]");
+        assert_eq!(msg(Discrete::with_start(3, 2).validate(&5)), "Discrete: 5 is outside [2, 5)");
+        assert_eq!(msg(MultiBinary::new(3).validate(&vec![0, 2, 1])), "MultiBinary dim 1: 2 is not 0 or 1");
+        assert_eq!(msg(MultiDiscrete::new([2, 3]).validate(&vec![1])), "MultiDiscrete: expected 2 values, got 1");
+
+        let dict = DictSpace::new([("pos", BoxSpace::new([0i32; 2], [9; 2])), ("vel", BoxSpace::new([-1; 2], [1; 2]))]);
+        let mut elem = BTreeMap::from([("pos".to_string(), [3, 4]), ("vel".to_string(), [0, 5])]);
+        assert_eq!(msg(dict.validate(&elem)), r#"DictSpace key "vel": BoxSpace dim 1: 5 > high 1"#);
+        elem.remove("pos");
+        assert_eq!(msg(dict.validate(&elem)), r#"DictSpace: missing key "pos""#);
+        let tuple = (Discrete::new(2), OneOf::new([Discrete::new(2), Discrete::new(4)]));
+        assert_eq!(msg(tuple.validate(&(1, (0, 3)))), "tuple component 1: OneOf variant 0: Discrete: 3 is outside [0, 2)");
+
+        // check_action surfaces the details through GymError::InvalidAction
+        let err = crate::core::check_action(&b, &[1.0, 2.0, 3.7]).unwrap_err();
+        assert!(matches!(&err, GymError::InvalidAction(m) if m.ends_with("BoxSpace dim 2: 3.7 > high 2.0")));
+    }
+
+    #[test]
+    fn spaces_sample_from_distributions() {
+        use crate::utils::rng_from_seed;
+        use rand::Rng;
+
+        let mut rng = rng_from_seed(7);
+        let draws = 4000;
+        let space = Discrete::with_start(3, 10);
+        let dist = space.categorical([1.0, 0.0, 3.0]);
+        let mut counts = [0usize; 3];
+        for _ in 0..draws {
+            let a = rng.sample(&dist);
+            assert!(space.contains(&a));
+            counts[(a - 10) as usize] += 1;
+        }
+        assert_eq!(counts[1], 0);
+        assert!((counts[2] as f64 / draws as f64 - 0.75).abs() < 0.03, "{counts:?}");
+
+        let md = MultiDiscrete::new([2, 3]).categorical([vec![0.0, 1.0], vec![0.0, 0.0, 1.0]]);
+        assert_eq!(rng.sample(&md), vec![1, 2]);
+
+        let bits = MultiBinary::new(3).bernoulli([0.0, 1.0, 0.2]);
+        let ones = (0..draws).map(|_| rng.sample(&bits)).inspect(|b| assert_eq!(&b[..2], &[0, 1])).filter(|b| b[2] == 1).count();
+        assert!((ones as f64 / draws as f64 - 0.2).abs() < 0.03, "{ones}");
+
+        // Gaussian samples are clipped to the bounds, also for integer dtypes
+        let b = BoxSpace::new([-1.0f32, 0.0], [1.0, f32::INFINITY]);
+        let gauss = b.gaussian([0.5, 100.0], [2.0, 1.0]);
+        let samples: Vec<[f32; 2]> = (0..draws).map(|_| rng.sample(&gauss)).collect();
+        assert!(samples.iter().all(|x| b.contains(x)));
+        assert!(samples.iter().any(|x| x[0] == 1.0) && samples.iter().any(|x| x[0] == -1.0));
+        let mean = samples.iter().map(|x| x[1] as f64).sum::<f64>() / draws as f64;
+        assert!((mean - 100.0).abs() < 0.1, "{mean}");
+        let pixels = ShapedBoxSpace::uniform([2, 2], 0u8, 255);
+        let noise = pixels.gaussian(vec![128.0; 4], vec![200.0; 4]);
+        assert!((0..100).all(|_| pixels.contains(&rng.sample(&noise))));
+
+        // Beta(2, 6) scaled to [-1, 3] has mean -1 + 4 * 0.25 = 0
+        let beta = ShapedBoxSpace::uniform([1], -1.0f64, 3.0).beta([2.0], [6.0]);
+        let samples: Vec<f64> = (0..draws).map(|_| rng.sample(&beta)[0]).collect();
+        assert!(samples.iter().all(|x| (-1.0..=3.0).contains(x)));
+        let mean = samples.iter().sum::<f64>() / draws as f64;
+        assert!(mean.abs() < 0.05, "{mean}");
+        let small = BoxSpace::new([0.0f64], [1.0]).beta([0.5], [0.5]);
+        assert!((0..100).all(|_| (0.0..=1.0).contains(&rng.sample(&small)[0])));
+        // Both gamma draws underflow to 0 for tiny parameters; samples still stay in the space.
+        let tiny_space = BoxSpace::new([-2.0f32, 0.0], [2.0, 1.0]);
+        let tiny = tiny_space.beta([1e-3, 1e-3], [1e-3, 1e-2]);
+        assert!((0..1000).all(|_| tiny_space.contains(&rng.sample(&tiny))));
+    }
+
+    #[test]
+    fn spaces_packed_multibinary() {
+        use crate::spaces::{flatten, unflatten, BitVector, DynConvert, PackedMultiBinary};
+        use crate::utils::rng_from_seed;
+
+        let space = PackedMultiBinary::new(4096);
+        assert_eq!(space, MultiBinary::new(4096).packed());
+        let mut rng = rng_from_seed(5);
+        let bits = space.sample(&mut rng);
+        assert!(space.contains(&bits));
+        assert_eq!(bits.words().len(), 64);
+        assert!((bits.count_ones() as f64 - 2048.0).abs() < 200.0, "{}", bits.count_ones());
+        assert!(!space.contains(&BitVector::zeros(4095)));
+        assert_eq!(space.validate(&BitVector::zeros(10)).unwrap_err().to_string(), "PackedMultiBinary: expected 4096 values, got 10");
+
+        // Bits past the length stay clear, so `!` and equality work on whole words
+        let small = PackedMultiBinary::new(70);
+        assert_eq!(!BitVector::zeros(70), BitVector::ones(70));
+        assert_eq!(BitVector::ones(70).count_ones(), 70);
+        assert_eq!(BitVector::from_words(vec![!0, !0], 70), BitVector::ones(70));
+        let mut x = BitVector::zeros(70);
+        x.set(3, true);
+        x.set(69, true);
+        assert_eq!(x.get(69), Some(true));
+        assert_eq!(x.get(70), None);
+        assert_eq!(x.ones_indices().collect::<Vec<_>>(), vec![3, 69]);
+        let y = small.sample(&mut rng);
+        assert_eq!((&x ^ &y) ^ &y, x);
+        assert_eq!(&(&x | &y) & &x, x);
+        let mut z = y.clone();
+        z &= &!&y;
+        assert!(!z.any());
+
+        let bytes = vec![1, 0, 1, 1];
+        let packed = BitVector::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(format!("{packed:?}"), "BitVector(1011)");
+        assert_eq!(Vec::<u8>::from(&packed), bytes);
+        assert!(BitVector::try_from(&[0u8, 2][..]).is_err());
+        let four = PackedMultiBinary::new(4);
+        assert_eq!(flatten(&four, &packed), vec![1.0, 0.0, 1.0, 1.0]);
+        assert_eq!(unflatten(&four, &[1.0, 0.0, 1.0, 1.0]).unwrap(), packed);
+        let value = four.to_dyn_value(packed.clone()).unwrap();
+        assert_eq!(value, DynValue::Ints(vec![1, 0, 1, 1]));
+        assert!(four.to_dyn_space().contains(&value));
+        assert_eq!(four.element_from_dyn(value).unwrap(), packed);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn spaces_serde_roundtrip_and_schema() {
+        use crate::spaces::{json_schema, GraphSpace, PackedMultiBinary, SequenceSpace, TextSpace};
+        use serde_json::json;
+
+        let d = Discrete::with_start(3, 1);
+        assert_eq!(serde_json::to_value(&d).unwrap(), json!({"type": "Discrete", "n": 3, "start": 1}));
+        assert_eq!(serde_json::from_value::<Discrete>(json!({"type": "Discrete", "n": 4})).unwrap(), Discrete::new(4));
+        assert!(serde_json::from_value::<Discrete>(json!({"type": "Discrete", "n": 0})).is_err());
+        assert!(serde_json::from_value::<Discrete>(json!({"type": "MultiBinary", "n": 4})).is_err());
+
+        // CartPole's observation space has infinite velocity bounds
+        let obs = CartPoleEnv::default().observation_space();
+        let value = serde_json::to_value(&obs).unwrap();
+        assert_eq!(value["type"], "Box");
+        assert_eq!(value["dtype"], "float32");
+        assert_eq!(value["shape"], json!([4]));
+        assert_eq!(value["high"][1], "inf");
+        assert_eq!(serde_json::from_value::<BoxSpace<f32, 4>>(value.clone()).unwrap(), obs);
+        assert_eq!(serde_json::from_value::<ShapedBoxSpace<f32>>(value.clone()).unwrap(), ShapedBoxSpace::from(obs));
+        assert!(serde_json::from_value::<BoxSpace<f64, 4>>(value.clone()).is_err());
+        assert!(serde_json::from_value::<BoxSpace<f32, 3>>(value).is_err());
+        let scalar = json!({"type": "Box", "shape": [2, 2], "dtype": "uint8", "low": 0, "high": 255});
+        assert_eq!(serde_json::from_value::<ShapedBoxSpace<u8>>(scalar).unwrap(), ShapedBoxSpace::uniform([2, 2], 0, 255));
+
+        let dict = DictSpace::new([("a", OneOf::new([Discrete::new(2), Discrete::new(3)]))]);
+        let tuple = TupleSpace::new((MultiDiscrete::with_start([2, 3], [0, 5]), MultiBinary::new(4)));
+        let text = TextSpace::with_charset(1, 4, "ab-".chars());
+        let seq = SequenceSpace::bounded(Discrete::new(2), 1, 3);
+        let graph = GraphSpace::new(BoxSpace::new([0.0f64], [1.0]), Some(Discrete::new(2))).with_max_nodes(4);
+        assert_eq!(serde_json::to_value(&tuple).unwrap()["spaces"][0]["start"], json!([0, 5]));
+        macro_rules! roundtrip {
+            ($($space:expr),*) => {$(
+                let back = serde_json::from_str(&serde_json::to_string(&$space).unwrap()).unwrap();
+                assert_eq!($space, back);
+            )*};
+        }
+        roundtrip!(dict, tuple, text, seq, graph);
+
+        let schema = json_schema(&BoxSpace::new([0.0f32, -1.0], [1.0, f32::INFINITY]));
+        assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+        assert_eq!(schema["prefixItems"][1], json!({"type": "number", "minimum": -1.0}));
+        assert_eq!(
+            json_schema(&ShapedBoxSpace::uniform([84, 84, 3], 0u8, 255))["items"],
+            json!({"type": "integer", "minimum": 0, "maximum": 255})
+        );
+        let schema = json_schema(&dict);
+        assert_eq!(schema["required"], json!(["a"]));
+        assert_eq!(schema["properties"]["a"]["oneOf"][1]["prefixItems"][1]["maximum"], 2);
+        assert_eq!(json_schema(&text)["pattern"], r"^[\-ab]*$");
+        assert_eq!(json_schema(&seq)["maxItems"], 3);
+        assert_eq!(json_schema(&graph)["properties"]["edges"]["items"]["maximum"], 1);
+
+        let packed = PackedMultiBinary::new(70);
+        assert_eq!(serde_json::to_value(&packed).unwrap(), json!({"type": "MultiBinary", "n": 70}));
+        let bits = crate::spaces::BitVector::from_words(vec![5, 1 << 5], 70);
+        let value = serde_json::to_value(&bits).unwrap();
+        assert_eq!(value, json!({"len": 70, "words": [5, 32]}));
+        assert_eq!(serde_json::from_value::<crate::spaces::BitVector>(value).unwrap(), bits);
+        assert!(serde_json::from_value::<crate::spaces::BitVector>(json!({"len": 70, "words": [5, 64]})).is_err());
+        assert!(serde_json::from_value::<crate::spaces::BitVector>(json!({"len": 70, "words": [5]})).is_err());
+        assert_eq!(json_schema(&packed)["properties"]["words"]["maxItems"], 2);
+
+        // DynSpace keeps the typed form; Box bounds are widened to float64
+        let dynamic = DynSpace::from((CartPoleEnv::default().observation_space(), Discrete::new(2), graph.clone()));
+        let value = serde_json::to_value(&dynamic).unwrap();
+        assert_eq!(value["spaces"][0]["dtype"], "float64");
+        assert_eq!(value["spaces"][2], serde_json::to_value(&graph).unwrap());
+        assert_eq!(serde_json::from_value::<DynSpace>(value).unwrap(), dynamic);
+        assert!(serde_json::from_value::<DynSpace>(json!({"type": "Box", "shape": [1], "dtype": "complex64", "low": 0, "high": 1})).is_err());
+    }
+
+    #[test]
+    fn spaces_dyn_convert_roundtrip() {
+        use crate::spaces::{DynConvert, GraphSpace, SequenceSpace, TextSpace};
+        use crate::utils::rng_from_seed;
+
+        let mut rng = rng_from_seed(3);
+        macro_rules! roundtrip {
+            ($($space:expr),*) => {$(
+                let space = $space;
+                let dynamic = space.to_dyn_space();
+                for _ in 0..10 {
+                    let elem = space.sample(&mut rng);
+                    let value = space.to_dyn_value(elem.clone()).unwrap();
+                    assert!(dynamic.contains(&value), "{value:?} not in {dynamic:?}");
+                    assert_eq!(space.element_from_dyn(value).unwrap(), elem);
+                    let value = dynamic.sample(&mut rng);
+                    assert!(space.contains(&space.element_from_dyn(value).unwrap()));
+                }
+            )*};
+        }
+        roundtrip!(
+            Discrete::with_start(3, 2),
+            MultiDiscrete::with_start([2, 3], [0, 5]),
+            MultiBinary::new(4),
+            BoxSpace::new([0.0f32, -1.0], [1.0, 1.0]),
+            ShapedBoxSpace::uniform([2, 2], 0u8, 255),
+            TextSpace::with_charset(1, 4, "ab".chars()),
+            (Discrete::new(2), BoxSpace::new([-1.0f64], [1.0])),
+            DictSpace::new([("a", OneOf::new([Discrete::new(2), Discrete::new(3)]))]),
+            SequenceSpace::bounded(MultiBinary::new(2), 0, 3),
+            GraphSpace::new(BoxSpace::new([0.0f64], [1.0]), Some(Discrete::new(2))).with_max_nodes(4)
+        );
+
+        let space = DynSpace::from(MultiDiscrete::new([2, 3]));
+        let err = space.validate(&DynValue::Floats(vec![0.0, 1.0])).unwrap_err();
+        assert_eq!(err.to_string(), "MultiDiscrete space cannot hold a Floats value");
+        assert_eq!(space.validate(&DynValue::Ints(vec![1, 3])).unwrap_err().to_string(), "MultiDiscrete dim 1: 3 is outside [0, 3)");
+        assert!(Discrete::new(2).element_from_dyn(DynValue::Text("1".into())).is_err());
+
+        // Box values convert only from the kind `validate` accepts, without rounding or saturating.
+        let space = BoxSpace::new([0u8], [255]);
+        let err = space.element_from_dyn(DynValue::Floats(vec![300.0])).unwrap_err();
+        assert_eq!(err.to_string(), space.to_dyn_space().validate(&DynValue::Floats(vec![300.0])).unwrap_err().to_string());
+        assert!(space.element_from_dyn(DynValue::Floats(vec![2.6])).is_err());
+        assert_eq!(space.element_from_dyn(DynValue::Ints(vec![300])).unwrap_err().to_string(), "Box dim 0: 300 is out of range");
+        assert_eq!(space.element_from_dyn(DynValue::Ints(vec![3])).unwrap(), [3]);
+        assert!(BoxSpace::new([0.0f32], [1.0]).element_from_dyn(DynValue::Ints(vec![1])).is_err());
+    }
+
     #[test]
     fn classic_control_cartpole_runs() {
         let mut env = CartPoleEnv::default();
@@ -148,6 +683,317 @@ mod tests {
         assert!(matches!(frame, Some(RenderFrame::Pixels { .. }) | Some(RenderFrame::Text(_))));
     }
 
+    /// Roll out random actions and check every observation stays in the declared space.
+    fn rollout_within_spaces<E: Env>(env: &mut E, steps: usize) {
+        let mut rng = StdRng::seed_from_u64(7);
+        let (obs, _) = env.reset(Some(0));
+        assert!(env.observation_space().contains(&obs));
+        for _ in 0..steps {
+            let action = env.action_space().sample(&mut rng);
+            let s = env.step(action);
+            assert!(env.observation_space().contains(&s.observation));
+            if s.terminated || s.truncated { env.reset(None); }
+        }
+    }
+
+    #[test]
+    fn builtin_envs_report_spaces() {
+        assert_eq!(CartPoleEnv::default().action_space(), Discrete::new(2));
+        assert_eq!(MountainCarEnv::default().action_space(), Discrete::new(3));
+        assert_eq!(LunarLanderEnv::default().action_space(), Discrete::new(4));
+        assert_eq!(MountainCarContinuousEnv::default().action_space(), BoxSpace::new([-1.0], [1.0]));
+
+        rollout_within_spaces(&mut CartPoleEnv::default(), 200);
+        rollout_within_spaces(&mut MountainCarEnv::default(), 200);
+        rollout_within_spaces(&mut MountainCarContinuousEnv::default(), 200);
+        rollout_within_spaces(&mut AcrobotEnv::default(), 200);
+        rollout_within_spaces(&mut PendulumEnv::default(), 200);
+        rollout_within_spaces(&mut LunarLanderEnv::default(), 200);
+    }
+
+    #[test]
+    fn wrappers_report_transformed_spaces() {
+        let env = TimeLimit::new(PendulumEnv::default(), 10);
+        assert_eq!(env.action_space(), Discrete::new(3));
+
+        let env = TransformObservation::new(
+            CartPoleEnv::default(),
+            |o: &[f32; 4]| [o[0], o[2]],
+            BoxSpace::new([-4.8, -0.42], [4.8, 0.42]),
+        );
+        assert_eq!(env.observation_space(), BoxSpace::new([-4.8, -0.42], [4.8, 0.42]));
+
+        // ClipAction accepts any action, so it reports the unbounded Box.
+        let mut env = ClipAction::new(MountainCarContinuousEnv::default(), [-0.5], [0.5]);
+        assert_eq!(env.action_space(), BoxSpace::new([f32::NEG_INFINITY], [f32::INFINITY]));
+        assert!(env.action_space().contains(&[5.0]));
+        rollout_within_spaces(&mut env, 50);
+    }
+
+    #[test]
+    fn try_step_rejects_invalid_usage() {
+        let mut env = CartPoleEnv::default();
+        assert!(matches!(env.try_step(0), Err(GymError::NotReady(_))));
+        env.try_reset(Some(0)).expect("reset ok");
+        assert!(matches!(env.try_step(2), Err(GymError::InvalidAction(_))));
+        while !env.try_step(1).map(|s| s.terminated || s.truncated).expect("valid step") {}
+        assert!(matches!(env.try_step(1), Err(GymError::NotReady(_))));
+
+        let mut wrapped = RecordEpisodeStatistics::new(TimeLimit::new(LunarLanderEnv::default(), 5));
+        assert!(matches!(wrapped.try_step(0), Err(GymError::NotReady(_))));
+        wrapped.try_reset(Some(1)).expect("reset ok");
+        assert!(matches!(wrapped.try_step(4), Err(GymError::InvalidAction(_))));
+        assert!(wrapped.try_step(0).is_ok());
+
+        let mut clipped = ClipAction::new(MountainCarContinuousEnv::default(), [-1.0], [1.0]);
+        clipped.try_reset(Some(0)).expect("reset ok");
+        assert!(clipped.try_step([5.0]).is_ok());
+    }
+
+    /// Snapshot, roll forward (through a reset to exercise the RNG), restore, and replay.
+    fn replay_from_snapshot<E>(env: &mut E, actions: &[E::Act])
+    where
+        E: StatefulEnv,
+        E::Act: Clone,
+        E::Obs: PartialEq + std::fmt::Debug,
+    {
+        env.reset(Some(11));
+        let snapshot = env.get_state();
+        let rollout = |env: &mut E| {
+            let mut obs = Vec::new();
+            for a in actions {
+                let s = env.step(a.clone());
+                obs.push(s.observation);
+                if s.terminated || s.truncated { obs.push(env.reset(None).0); }
+            }
+            obs.push(env.reset(None).0);
+            obs
+        };
+        let first = rollout(env);
+        env.set_state(&snapshot);
+        assert_eq!(first, rollout(env));
+    }
+
+    #[test]
+    fn stateful_envs_replay_exactly() {
+        replay_from_snapshot(&mut CartPoleEnv::default(), &[1, 0, 1, 1, 0, 0, 1]);
+        replay_from_snapshot(&mut AcrobotEnv::default(), &[0, 2, 1, 2, 0]);
+        replay_from_snapshot(&mut LunarLanderEnv::default(), &[2, 2, 1, 3, 0]);
+        replay_from_snapshot(&mut MountainCarContinuousEnv::default(), &[[0.3], [-1.0]]);
+        replay_from_snapshot(&mut TimeLimit::new(MountainCarEnv::default(), 3), &[2, 2, 0, 1]);
+        replay_from_snapshot(&mut RecordEpisodeStatistics::new(PendulumEnv::default()), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn wrapper_state_includes_bookkeeping() {
+        let mut env = TimeLimit::new(CartPoleEnv::default(), 2);
+        env.reset(Some(0));
+        let snapshot = env.get_state();
+        assert!(!env.step(1).truncated);
+        assert!(env.step(1).truncated);
+        env.set_state(&snapshot);
+        assert!(!env.step(1).truncated);
+    }
+
+    #[test]
+    fn reset_options_set_initial_state() {
+        let mut env = CartPoleEnv::default();
+        let (obs, _) = env.reset_with_options(Some(0), &ResetOptions::with_state([0.1, 0.0, -0.05, 0.2]));
+        assert_eq!(obs, [0.1, 0.0, -0.05, 0.2]);
+
+        let (obs, _) = env.reset_with_options(None, &ResetOptions::with_bounds(0.01, 0.02));
+        assert!(obs.iter().all(|&v| (0.01..=0.02).contains(&v)));
+
+        // Default options behave exactly like a plain reset.
+        let mut a = AcrobotEnv::default();
+        let mut b = AcrobotEnv::default();
+        assert_eq!(a.reset(Some(5)).0, b.reset_with_options(Some(5), &ResetOptions::new()).0);
+
+        let (obs, _) = a.reset_with_options(Some(5), &ResetOptions::with_bounds(-0.1, 0.1));
+        assert!(obs.iter().all(|&v| v.abs() <= 0.1));
+
+        let mut env = TimeLimit::new(MountainCarEnv::default(), 10);
+        let (obs, _) = env.reset_with_options(Some(0), &ResetOptions::with_bounds(-0.2, -0.1));
+        assert!((-0.2..=-0.1).contains(&obs[0]) && obs[1] == 0.0);
+
+        let mut env = LunarLanderEnv::default();
+        let (obs, _) = env.reset_with_options(None, &ResetOptions::with_state([0.1, 0.5, 0.0, -0.2, 0.0, 0.0]));
+        assert_eq!(obs, [0.1, 0.5, 0.0, -0.2, 0.0, 0.0, 0.0, 0.0]);
+        assert!(env.try_step(0).is_ok());
+    }
+
+    #[test]
+    fn render_mode_selects_frames() {
+        let env = TimeLimit::new(AcrobotEnv::default(), 10);
+        assert_eq!(env.render_mode(), RenderMode::RgbArray);
+        assert_eq!(env.metadata().frame_size, Some((400, 400)));
+        assert!(matches!(env.render(), Some(RenderFrame::Pixels { width: 400, height: 400, .. })));
+
+        let env = MountainCarEnv::default().with_render_mode(RenderMode::None);
+        assert_eq!(env.render_mode(), RenderMode::None);
+        assert!(env.render().is_none());
+        assert!(env.metadata().supports(RenderMode::None));
+
+        assert_eq!("ansi".parse::<RenderMode>().ok(), Some(RenderMode::Ansi));
+        assert!("human".parse::<RenderMode>().is_err());
+    }
+
+    #[test]
+    fn ansi_mode_renders_text_frames() {
+        fn text_of(frame: Option<RenderFrame>) -> String {
+            match frame {
+                Some(RenderFrame::Text(s)) => s,
+                other => panic!("expected a text frame, got {:?}", other.map(|_| ())),
+            }
+        }
+        let mut env = CartPoleEnv::default().with_render_mode(RenderMode::Ansi);
+        env.reset(Some(0));
+        let text = text_of(env.render());
+        assert!(text.contains("[_]") && text.lines().last().is_some_and(|l| l.starts_with("x=")));
+        assert_eq!(text, env.render_text());
+
+        let frames = [
+            text_of(MountainCarEnv::default().with_render_mode(RenderMode::Ansi).render()),
+            text_of(MountainCarContinuousEnv::default().with_render_mode(RenderMode::Ansi).render()),
+            text_of(AcrobotEnv::default().with_render_mode(RenderMode::Ansi).render()),
+            text_of(PendulumEnv::default().with_render_mode(RenderMode::Ansi).render()),
+            text_of(LunarLanderEnv::default().with_render_mode(RenderMode::Ansi).render()),
+        ];
+        assert!(frames.iter().all(|f| f.lines().count() > 2 && f.lines().all(|l| l.len() <= 120)));
+
+        let mut env = make("Pendulum-v1", KwArgs::from([("render_mode".to_string(), "ansi".to_string())])).expect("make");
+        env.reset(Some(0));
+        assert!(matches!(env.render(), Some(RenderFrame::Text(_))));
+    }
+
+    #[test]
+    fn order_enforcing_and_passive_checker() {
+        let mut env = OrderEnforcing::new(CartPoleEnv::default());
+        assert!(!env.has_reset());
+        assert!(matches!(env.try_step(0), Err(GymError::NotReady(_))));
+        assert!(env.render().is_none());
+        assert!(matches!(env.try_render(), Err(GymError::NotReady(_))));
+        env.reset(Some(0));
+        assert!(env.try_step(0).is_ok());
+        assert!(env.render().is_some());
+        assert!(matches!(env.try_render(), Ok(Some(_))));
+
+        // The snapshot carries the reset flag.
+        let mut fresh = OrderEnforcing::new(CartPoleEnv::default());
+        fresh.set_state(&env.get_state());
+        assert!(fresh.try_step(1).is_ok());
+
+        let mut env = PassiveEnvChecker::new(CartPoleEnv::default());
+        env.try_reset(Some(0)).expect("reset ok");
+        assert!(matches!(env.try_step(5), Err(GymError::InvalidAction(_))));
+        assert!(env.try_step(1).is_ok());
+
+        // The plain API records the violation and passes the action on.
+        let mut env = PassiveEnvChecker::new(CartPoleEnv::default());
+        env.reset(Some(0));
+        env.step(5);
+        env.step(7);
+        assert_eq!(env.warnings().len(), 1);
+        assert!(matches!(env.warnings()[0], GymError::InvalidAction(_)));
+    }
+
+    #[test]
+    #[should_panic(expected = "PassiveEnvChecker: action is outside the action space")]
+    fn passive_env_checker_panics_when_configured() {
+        let mut env = PassiveEnvChecker::new(CartPoleEnv::default()).with_panic(true);
+        env.reset(Some(0));
+        env.step(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot call step before reset")]
+    fn order_enforcing_panics_when_configured() {
+        OrderEnforcing::new(MountainCarEnv::default()).with_panic(true).try_step(0).ok();
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot call render before reset")]
+    fn order_enforcing_panics_on_render_when_configured() {
+        OrderEnforcing::new(MountainCarEnv::default()).with_panic(true).render();
+    }
+
+    #[test]
+    fn vector_rewards_flow_through_wrappers() {
+        // Landing + shaping reproduces the scalar LunarLander reward exactly.
+        let mut scalar = LunarLanderEnv::default();
+        let mut mo = LinearReward::new(MoLunarLanderEnv::default(), [1.0, 1.0, 0.0, 0.0]);
+        scalar.reset(Some(4));
+        mo.reset(Some(4));
+        for a in [2, 2, 1, 0, 3] {
+            let (s, m) = (scalar.step(a), mo.step(a));
+            assert_eq!(s.reward, m.reward);
+            assert_eq!(m.info.get_f64_array("vector_reward").map(<[f64]>::len), Some(4));
+        }
+
+        // Out-of-range actions fire the right engine, and are charged as such.
+        let mut env = MoLunarLanderEnv::default();
+        env.reset(Some(0));
+        assert_eq!(env.step(7).reward[3], -1.0);
+
+        let mut env = ClipReward::new(MoLunarLanderEnv::default(), -0.5, 0.5);
+        env.reset(Some(0));
+        let s = env.step(2);
+        assert_eq!(s.reward[2], -0.5);
+        assert!(s.reward.iter().all(|r| (-0.5..=0.5).contains(r)));
+
+        let mut env = RecordEpisodeStatistics::new(TimeLimit::new(
+            TransformReward::new(MoLunarLanderEnv::default(), |r| r * 2.0),
+            3,
+        ));
+        env.reset(Some(0));
+        env.step(1);
+        env.step(1);
+        let s = env.step(1);
+        assert_eq!(s.reward[3], -2.0);
+        let returns = s.info.get_f64_array("episode_return").expect("vector return");
+        assert_eq!(returns.len(), 4);
+        assert_eq!(returns[3], -6.0);
+
+        let mut env = RecordEpisodeStatistics::new(TimeLimit::new(CartPoleEnv::default(), 2));
+        env.reset(Some(0));
+        env.step(0);
+        assert_eq!(env.step(0).info.get_f64("episode_return"), Some(2.0));
+    }
+
+    #[test]
+    fn reset_options_reject_invalid_values() {
+        let mut env = PendulumEnv::default();
+        let invalid = [
+            ResetOptions::with_state([0.0]),
+            ResetOptions::with_state([0.0, f32::NAN]),
+            ResetOptions::with_bounds(0.2, 0.1),
+            ResetOptions::with_bounds(f32::NEG_INFINITY, 0.0),
+            ResetOptions { high: Some(f32::NAN), ..ResetOptions::new() },
+        ];
+        for options in &invalid {
+            let err = env.try_reset_with_options(None, options).unwrap_err();
+            assert!(matches!(err, GymError::InvalidArgument(_)), "{options:?}: {err:?}");
+        }
+        let err = env.try_reset_with_options(None, &invalid[0]).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid argument: reset options: expected an initial state of 2 values, got 1");
+
+        // Through wrappers and the registry as well
+        let mut env = TimeLimit::new(AcrobotEnv::default(), 10);
+        assert!(matches!(env.try_reset_with_options(None, &invalid[2]), Err(GymError::InvalidArgument(_))));
+        let mut env = make("CartPole-v1", KwArgs::new()).expect("registered");
+        assert!(matches!(env.try_reset_with_options(None, &invalid[1]), Err(GymError::InvalidArgument(_))));
+        let (obs, _) = env.try_reset_with_options(Some(0), &ResetOptions::with_bounds(0.0, 0.01)).expect("valid options");
+        assert!(matches!(obs, DynValue::Floats(v) if v.iter().all(|&x| (0.0..=0.01).contains(&x))));
+    }
+
+    #[test]
+    fn acrobot_reset_bounds_apply_to_angles_only() {
+        let mut env = AcrobotEnv::default();
+        let (obs, _) = env.reset_with_options(Some(3), &ResetOptions::with_bounds(1.0, 1.5));
+        assert!(obs[..2].iter().all(|v| (1.0..=1.5).contains(v)));
+        assert!(obs[2..].iter().all(|v| v.abs() <= 0.1));
+    }
+
     #[cfg(not(feature = "image"))]
     #[test]
     fn encode_png_without_feature_not_supported() {
@@ -180,7 +1026,7 @@ mod mountain_car_continuous_tests {
         let mut env = MountainCarContinuousEnv::default();
         let (_o, _info) = env.reset(Some(0));
         for _ in 0..10 {
-            let s = env.step(0.5);
+            let s = env.step([0.5]);
             // Reward can be negative due to action penalty; ensure obs length is 2
             assert!(s.observation.len() == 2);
             if s.terminated || s.truncated { break; }
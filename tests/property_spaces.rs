@@ -1,4 +1,4 @@
-use rust_gymnasium::{BoxSpace, Discrete, MultiBinary, MultiDiscrete, spaces::Space};
+use rust_gymnasium::{BoxSpace, Discrete, MultiBinary, MultiDiscrete, ShapedBoxSpace, spaces::{BitVector, Flatten, Space}};
 use proptest::prelude::*;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
@@ -32,6 +32,62 @@ proptest! {
         }
     }
 
+    // Flattening is invertible and stays inside the flattened space
+    #[test]
+    fn multidiscrete_flatten_roundtrip(nvec in proptest::collection::vec(1u32..20, 1..6), seed in any::<u64>()) {
+        let md = MultiDiscrete::new(nvec.clone());
+        let flat_space = md.flatten_space();
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..20 {
+            let v = md.sample(&mut rng);
+            let flat = md.flatten(&v);
+            prop_assert_eq!(flat.len(), md.flatdim());
+            prop_assert!(flat_space.contains(&flat));
+            prop_assert_eq!(md.unflatten(&flat).unwrap(), v);
+        }
+    }
+
+    // validate agrees with contains on arbitrary (mostly invalid) elements
+    #[test]
+    fn discrete_validate_matches_contains(n in 1u32..50, start in 0u32..50, v in 0u32..150) {
+        let d = Discrete::with_start(n, start);
+        prop_assert_eq!(d.validate(&v).is_ok(), d.contains(&v));
+    }
+
+    #[test]
+    fn multidiscrete_validate_matches_contains(
+        nvec in proptest::collection::vec(1u32..10, 1..5),
+        v in proptest::collection::vec(0u32..12, 0..6),
+    ) {
+        let md = MultiDiscrete::new(nvec);
+        let result = md.validate(&v);
+        prop_assert_eq!(result.is_ok(), md.contains(&v));
+        if let Err(e) = result {
+            prop_assert!(e.to_string().starts_with("MultiDiscrete"), "{}", e);
+        }
+    }
+
+    #[test]
+    fn multibinary_validate_matches_contains(n in 1usize..16, v in proptest::collection::vec(0u8..3, 0..20)) {
+        let mb = MultiBinary::new(n);
+        prop_assert_eq!(mb.validate(&v).is_ok(), mb.contains(&v));
+    }
+
+    // Rejected Box elements name the first offending dimension and its bound
+    #[test]
+    fn boxspace_validate_reports_dimension(v in proptest::array::uniform3(-3.0f32..3.0), seed in any::<u64>()) {
+        let b = BoxSpace::new([-1.0, -2.0, 0.0], [1.0, 2.0, 2.0]);
+        prop_assert_eq!(b.validate(&v).is_ok(), b.contains(&v));
+        if let Some(i) = (0..3).find(|&i| v[i] < b.low()[i] || v[i] > b.high()[i]) {
+            let msg = b.validate(&v).unwrap_err().to_string();
+            prop_assert!(msg.starts_with(&format!("BoxSpace dim {i}: {:?} ", v[i])), "{}", msg);
+        }
+        let shaped = ShapedBoxSpace::from(b.clone());
+        prop_assert_eq!(shaped.validate(&v.to_vec()).is_ok(), b.contains(&v));
+        let mut rng = StdRng::seed_from_u64(seed);
+        prop_assert!(b.validate(&b.sample(&mut rng)).is_ok());
+    }
+
     // MultiDiscrete per-dimension ranges honored
     #[test]
     fn multidiscrete_sampling_valid(nvec in proptest::collection::vec(1u32..10_000, 1..8), seed in any::<u64>()) {
@@ -43,6 +99,26 @@ proptest! {
             prop_assert_eq!(v.len(), nvec.len());
         }
     }
+
+    // Packing is lossless and bitwise operators agree with the unpacked bytes
+    #[test]
+    fn bitvector_roundtrip_and_ops(
+        a in proptest::collection::vec(0u8..=1, 0..300),
+        b_seed in any::<u64>(),
+    ) {
+        let bits = BitVector::try_from(a.as_slice()).unwrap();
+        prop_assert_eq!(bits.len(), a.len());
+        prop_assert_eq!(bits.to_bytes(), a.clone());
+        prop_assert_eq!(bits.count_ones(), a.iter().filter(|&&v| v == 1).count());
+
+        let b = MultiBinary::new(a.len().max(1)).sample(&mut StdRng::seed_from_u64(b_seed))[..a.len()].to_vec();
+        let other = BitVector::try_from(b.as_slice()).unwrap();
+        let zip = |f: fn(u8, u8) -> u8| a.iter().zip(&b).map(|(&x, &y)| f(x, y)).collect::<Vec<u8>>();
+        prop_assert_eq!((&bits & &other).to_bytes(), zip(|x, y| x & y));
+        prop_assert_eq!((&bits | &other).to_bytes(), zip(|x, y| x | y));
+        prop_assert_eq!((&bits ^ &other).to_bytes(), zip(|x, y| x ^ y));
+        prop_assert_eq!((!&bits).to_bytes(), a.iter().map(|&x| 1 - x).collect::<Vec<u8>>());
+    }
 }
 
 // BoxSpace needs concrete element type and const N known at compile time.
@@ -72,3 +148,28 @@ fn boxspace_sampling_contains_and_deterministic() {
         assert_eq!(v1, v2);
     }
 }
+
+// Infinite bounds: normal samples when unbounded, exponential offsets when half-bounded.
+#[test]
+fn boxspace_infinite_bounds_sampling() {
+    let inf = f64::INFINITY;
+    let b = BoxSpace::new([-inf, 1.0, -inf, -2.0], [inf, inf, 3.0, 2.0]);
+    let mut rng = StdRng::seed_from_u64(7);
+    let n = 20_000;
+    let mut mean = [0.0f64; 4];
+    for _ in 0..n {
+        let v = b.sample(&mut rng);
+        assert!(b.contains(&v) && v.iter().all(|x| x.is_finite()));
+        assert!(v[1] >= 1.0 && v[2] <= 3.0);
+        for (m, x) in mean.iter_mut().zip(v) {
+            *m += x / n as f64;
+        }
+    }
+    // N(0, 1), 1 + Exp(1), 3 - Exp(1), Uniform[-2, 2]
+    for (m, expected) in mean.iter().zip([0.0, 2.0, 2.0, 0.0]) {
+        assert!((m - expected).abs() < 0.05, "mean {m} vs {expected}");
+    }
+
+    let point = BoxSpace::new([inf], [inf]);
+    assert_eq!(point.sample(&mut rng), [inf]);
+}
@@ -9,7 +9,7 @@ fn single_vs_vector_n1_same_rollout() {
     let (_obs_s, _info_s) = single.reset(Some(0));
 
     // Vector env with N=1
-    let mut vec_env = SyncVectorEnv::new(1, || CartPoleEnv::default());
+    let mut vec_env = SyncVectorEnv::new(1, CartPoleEnv::default);
     let _obs_all = vec_env.reset_all(Some(0));
 
     // Use a fixed action sequence
@@ -28,7 +28,7 @@ fn single_vs_vector_n1_same_rollout() {
 // Basic sanity for N=2 shape/length behavior
 #[test]
 fn vector_two_envs_steps_lengths() {
-    let mut v = SyncVectorEnv::new(2, || CartPoleEnv::default());
+    let mut v = SyncVectorEnv::new(2, CartPoleEnv::default);
     let obs_infos = v.reset_all(Some(123));
     assert_eq!(obs_infos.len(), 2);
     let steps = v.step_all(vec![0, 1]);
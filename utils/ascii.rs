@@ -0,0 +1,70 @@
+/// A fixed-size character grid for ASCII-art frames (`RenderMode::Ansi`).
+/// Row 0 is the top line; drawing outside the grid is silently clipped.
+pub struct TextCanvas {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<char>,
+}
+
+impl TextCanvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self { width, height, cells: vec![' '; width * height] }
+    }
+
+    #[inline]
+    pub fn put(&mut self, x: i32, y: i32, c: char) {
+        if x < 0 || y < 0 { return; }
+        let (x, y) = (x as usize, y as usize);
+        if x >= self.width || y >= self.height { return; }
+        self.cells[y * self.width + x] = c;
+    }
+
+    /// Draw a horizontal run of `c` from x0 to x1 (inclusive) on row y.
+    pub fn hline(&mut self, x0: i32, x1: i32, y: i32, c: char) {
+        for x in x0.min(x1)..=x0.max(x1) {
+            self.put(x, y, c);
+        }
+    }
+
+    /// Draw a line of `c` between two cells (Bresenham).
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, c: char) {
+        let (mut x, mut y) = (x0, y0);
+        let dx = (x1 - x0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let dy = -(y1 - y0).abs();
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            self.put(x, y, c);
+            if x == x1 && y == y1 { break; }
+            let e2 = 2 * err;
+            if e2 >= dy { err += dy; x += sx; }
+            if e2 <= dx { err += dx; y += sy; }
+        }
+    }
+
+    /// Write `text` starting at (x, y), clipped at the right edge.
+    pub fn text(&mut self, x: i32, y: i32, text: &str) {
+        for (i, c) in text.chars().enumerate() {
+            self.put(x + i as i32, y, c);
+        }
+    }
+
+    /// Render the grid (trailing spaces trimmed) followed by a one-line state readout.
+    pub fn render(&self, readout: &str) -> String {
+        let mut out = String::with_capacity((self.width + 1) * (self.height + 1) + readout.len());
+        for row in self.cells.chunks(self.width.max(1)) {
+            let line: String = row.iter().collect();
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out.push_str(readout);
+        out
+    }
+}
+
+/// Map a world coordinate in [lo, hi] to a cell index in [0, cells), clamping out-of-range values.
+pub fn to_cell(v: f32, lo: f32, hi: f32, cells: usize) -> i32 {
+    let t = ((v - lo) / (hi - lo)).clamp(0.0, 1.0);
+    ((t * (cells.saturating_sub(1)) as f32).round()) as i32
+}
@@ -0,0 +1,376 @@
+//! Environment checker, in the spirit of Gymnasium's `utils.env_checker.check_env`.
+//!
+//! `check_env` drives any `Env` through a seeded reset and a random rollout and collects every
+//! contract violation it finds into a `CheckReport` instead of stopping at the first one.
+
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::core::{Env, GymError, RenderFrame, RenderMode, Reward};
+use crate::spaces::Space;
+use crate::utils::rng::rng_from_seed;
+
+/// Values whose floating-point components can be checked for NaN/inf.
+/// Integer and boolean values are always finite.
+pub trait CheckFinite {
+    fn all_finite(&self) -> bool;
+}
+
+macro_rules! finite_float {
+    ($($t:ty),*) => {$(
+        impl CheckFinite for $t { fn all_finite(&self) -> bool { self.is_finite() } }
+    )*};
+}
+
+macro_rules! finite_always {
+    ($($t:ty),*) => {$(
+        impl CheckFinite for $t { fn all_finite(&self) -> bool { true } }
+    )*};
+}
+
+finite_float!(f32, f64);
+finite_always!(bool, char, String, u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl CheckFinite for crate::spaces::BitVector { fn all_finite(&self) -> bool { true } }
+
+impl<T: CheckFinite, const N: usize> CheckFinite for [T; N] {
+    fn all_finite(&self) -> bool { self.iter().all(CheckFinite::all_finite) }
+}
+
+impl<T: CheckFinite> CheckFinite for [T] {
+    fn all_finite(&self) -> bool { self.iter().all(CheckFinite::all_finite) }
+}
+
+impl<T: CheckFinite> CheckFinite for Vec<T> {
+    fn all_finite(&self) -> bool { self.as_slice().all_finite() }
+}
+
+impl<K, T: CheckFinite> CheckFinite for std::collections::BTreeMap<K, T> {
+    fn all_finite(&self) -> bool { self.values().all(CheckFinite::all_finite) }
+}
+
+macro_rules! finite_tuple {
+    ($($T:ident $i:tt),+) => {
+        impl<$($T: CheckFinite),+> CheckFinite for ($($T,)+) {
+            fn all_finite(&self) -> bool { $(self.$i.all_finite())&&+ }
+        }
+    };
+}
+
+finite_tuple!(A 0);
+finite_tuple!(A 0, B 1);
+finite_tuple!(A 0, B 1, C 2);
+finite_tuple!(A 0, B 1, C 2, D 3);
+
+/// The individual checks run by `check_env`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Check {
+    /// Two resets with the same seed return identical observations.
+    ResetDeterminism,
+    /// Every observation belongs to `observation_space()`.
+    ObservationSpace,
+    /// Observations contain no NaN or infinite values.
+    FiniteObservation,
+    /// Rewards contain no NaN or infinite values.
+    FiniteReward,
+    /// Actions sampled from `action_space()` are accepted by `try_step`.
+    ActionSpace,
+    /// `try_step` succeeds during the rollout after `reset` (fails with errors other than
+    /// `GymError::InvalidAction`, e.g. `GymError::NotReady`).
+    StepAfterReset,
+    /// `try_step` after termination returns `GymError::NotReady`.
+    StepAfterTermination,
+    /// Render frames match `render_mode()` and pixel buffers match `width * height * {3, 4}`.
+    RenderFrame,
+    /// Calling `close` twice does not panic.
+    CloseIdempotent,
+}
+
+/// A failed check and the error describing it.
+#[derive(Debug)]
+pub struct CheckFailure {
+    pub check: Check,
+    pub error: GymError,
+}
+
+/// Outcome of `check_env`: every failed check, in the order it was found.
+/// Each check is reported at most once.
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    pub failures: Vec<CheckFailure>,
+}
+
+impl CheckReport {
+    /// Whether every check passed.
+    pub fn is_ok(&self) -> bool { self.failures.is_empty() }
+
+    /// Whether `check` failed.
+    pub fn failed(&self, check: Check) -> bool { self.failures.iter().any(|f| f.check == check) }
+
+    /// The errors of all failed checks.
+    pub fn errors(&self) -> impl Iterator<Item = &GymError> { self.failures.iter().map(|f| &f.error) }
+
+    fn record(&mut self, check: Check, error: GymError) {
+        if !self.failed(check) {
+            self.failures.push(CheckFailure { check, error });
+        }
+    }
+}
+
+impl fmt::Display for CheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_ok() {
+            return f.write_str("all environment checks passed");
+        }
+        write!(f, "{} environment check(s) failed:", self.failures.len())?;
+        for failure in &self.failures {
+            write!(f, "\n- {:?}: {}", failure.check, failure.error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Settings for `check_env_with`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckOptions {
+    /// Seed used for the resets and for sampling actions.
+    pub seed: u64,
+    /// Maximum number of steps of the random rollout.
+    pub max_steps: usize,
+}
+
+impl Default for CheckOptions {
+    fn default() -> Self { Self { seed: 0, max_steps: 1000 } }
+}
+
+/// Check `env` with the default `CheckOptions`. See `check_env_with`.
+pub fn check_env<E>(env: &mut E) -> CheckReport
+where
+    E: Env,
+    E::Obs: PartialEq + fmt::Debug + CheckFinite,
+{
+    check_env_with(env, &CheckOptions::default())
+}
+
+/// Exercise `env` and report contract violations:
+/// - `reset` with the same seed is deterministic
+/// - observations are inside `observation_space()` and finite; rewards are finite
+/// - sampled actions are accepted, `try_step` succeeds after `reset` and returns
+///   `GymError::NotReady` after termination
+///   (the latter only checked when the rollout terminates within `max_steps`)
+/// - `render` output agrees with `render_mode()` and pixel buffers have a valid length
+/// - `close` can be called twice
+///
+/// The environment is left closed.
+pub fn check_env_with<E>(env: &mut E, options: &CheckOptions) -> CheckReport
+where
+    E: Env,
+    E::Obs: PartialEq + fmt::Debug + CheckFinite,
+{
+    let mut report = CheckReport::default();
+    let obs_space = env.observation_space();
+    let act_space = env.action_space();
+    let mut rng = rng_from_seed(options.seed);
+
+    let check_obs = |report: &mut CheckReport, obs: &E::Obs, context: &str| {
+        if let Err(e) = obs_space.validate(obs) {
+            report.record(
+                Check::ObservationSpace,
+                GymError::InvalidObservation(format!("{context} returned {obs:?}, outside the observation space: {e}")),
+            );
+        }
+        if !obs.all_finite() {
+            report.record(
+                Check::FiniteObservation,
+                GymError::InvalidObservation(format!("{context} returned a non-finite observation {obs:?}")),
+            );
+        }
+    };
+
+    let (first, _) = env.reset(Some(options.seed));
+    let (second, _) = env.reset(Some(options.seed));
+    check_obs(&mut report, &first, "reset");
+    if first != second {
+        report.record(
+            Check::ResetDeterminism,
+            GymError::Other(format!("reset(Some({})) returned {first:?}, then {second:?}", options.seed)),
+        );
+    }
+
+    let mut terminated = false;
+    for t in 0..options.max_steps {
+        let step = match env.try_step(act_space.sample(&mut rng)) {
+            Ok(step) => step,
+            Err(e @ GymError::InvalidAction(_)) => {
+                report.record(Check::ActionSpace, GymError::InvalidAction(format!("step {t} rejected a sampled action: {e}")));
+                break;
+            }
+            Err(GymError::NotReady(e)) => {
+                report.record(Check::StepAfterReset, GymError::NotReady(format!("step {t} after reset: {e}")));
+                break;
+            }
+            Err(e) => {
+                report.record(Check::StepAfterReset, GymError::Other(format!("step {t} after reset failed: {e}")));
+                break;
+            }
+        };
+        check_obs(&mut report, &step.observation, "step");
+        if !step.reward.components().iter().all(|&r| Into::<f64>::into(r).is_finite()) {
+            report.record(
+                Check::FiniteReward,
+                GymError::Other(format!("step {t} returned a non-finite reward {:?}", step.reward.components())),
+            );
+        }
+        if step.terminated {
+            terminated = true;
+            break;
+        }
+        if step.truncated { break; }
+    }
+    if terminated {
+        match env.try_step(act_space.sample(&mut rng)) {
+            Err(GymError::NotReady(_)) => {}
+            Err(e) => report.record(
+                Check::StepAfterTermination,
+                GymError::NotReady(format!("try_step after termination failed with {e:?} instead of NotReady")),
+            ),
+            Ok(_) => report.record(
+                Check::StepAfterTermination,
+                GymError::NotReady("try_step after termination succeeded instead of returning NotReady".into()),
+            ),
+        }
+    }
+
+    if let Some(error) = check_render_frame(env.render_mode(), env.render()) {
+        report.record(Check::RenderFrame, error);
+    }
+
+    for call in ["first", "second"] {
+        if catch_unwind(AssertUnwindSafe(|| env.close())).is_err() {
+            report.record(Check::CloseIdempotent, GymError::Other(format!("the {call} call to close panicked")));
+        }
+    }
+    report
+}
+
+/// Validate a frame against the render mode that produced it.
+fn check_render_frame(mode: RenderMode, frame: Option<RenderFrame>) -> Option<GymError> {
+    match (mode, frame) {
+        (_, None) | (RenderMode::Ansi, Some(RenderFrame::Text(_))) => None,
+        (RenderMode::RgbArray, Some(RenderFrame::Pixels { width, height, data })) => {
+            let count = width as usize * height as usize;
+            (data.len() != count * 3 && data.len() != count * 4).then(|| {
+                GymError::InvalidRender(format!(
+                    "pixel data length {} does not match width*height*3 or *4 ({width}x{height})",
+                    data.len()
+                ))
+            })
+        }
+        (mode, Some(_)) => Some(GymError::InvalidRender(format!("render returned the wrong frame kind for mode {mode}"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Info, Step};
+    use crate::spaces::{BoxSpace, Discrete};
+    use crate::envs::{CartPoleEnv, LunarLanderEnv, PendulumEnv};
+
+    /// Breaks several contracts: NaN observations past step 2, no NotReady after termination,
+    /// a truncated pixel buffer and a `close` that panics on the second call.
+    struct BrokenEnv {
+        t: u32,
+        closed: bool,
+    }
+
+    impl Env for BrokenEnv {
+        type Obs = [f32; 1];
+        type Act = u32;
+        type ObsSpace = BoxSpace<f32, 1>;
+        type ActSpace = Discrete;
+        type Reward = f32;
+
+        fn observation_space(&self) -> Self::ObsSpace { BoxSpace::new([-1.0], [1.0]) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(2) }
+
+        fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) {
+            self.t = 0;
+            ([0.0], Info::new())
+        }
+
+        fn step(&mut self, _action: Self::Act) -> Step<Self::Obs> {
+            self.t += 1;
+            let obs = if self.t > 2 { f32::NAN } else { 0.5 };
+            Step::new([obs], 1.0, self.t >= 5, false, Info::new())
+        }
+
+        fn render_mode(&self) -> RenderMode { RenderMode::RgbArray }
+
+        fn render(&self) -> Option<RenderFrame> {
+            Some(RenderFrame::Pixels { width: 2, height: 2, data: vec![0; 10] })
+        }
+
+        fn close(&mut self) {
+            assert!(!self.closed, "already closed");
+            self.closed = true;
+        }
+    }
+
+    /// Steps fail with `NotReady` even right after `reset`.
+    struct NeverReadyEnv;
+
+    impl Env for NeverReadyEnv {
+        type Obs = [f32; 1];
+        type Act = u32;
+        type ObsSpace = BoxSpace<f32, 1>;
+        type ActSpace = Discrete;
+        type Reward = f32;
+
+        fn observation_space(&self) -> Self::ObsSpace { BoxSpace::new([-1.0], [1.0]) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(2) }
+        fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) { ([0.0], Info::new()) }
+        fn step(&mut self, _action: Self::Act) -> Step<Self::Obs> { Step::new([0.0], 0.0, false, false, Info::new()) }
+
+        fn try_step(&mut self, _action: Self::Act) -> crate::core::Result<Step<Self::Obs>> {
+            Err(GymError::NotReady("never ready".into()))
+        }
+    }
+
+    #[test]
+    fn builtin_envs_pass() {
+        for report in [
+            check_env(&mut CartPoleEnv::default()),
+            check_env(&mut PendulumEnv::default().with_render_mode(RenderMode::Ansi)),
+            check_env(&mut LunarLanderEnv::default()),
+        ] {
+            assert!(report.is_ok(), "{report}");
+        }
+    }
+
+    #[test]
+    fn broken_env_reports_each_problem_once() {
+        let report = check_env(&mut BrokenEnv { t: 0, closed: false });
+        let checks: Vec<Check> = report.failures.iter().map(|f| f.check).collect();
+        assert_eq!(
+            checks,
+            vec![
+                Check::ObservationSpace,
+                Check::FiniteObservation,
+                Check::StepAfterTermination,
+                Check::RenderFrame,
+                Check::CloseIdempotent,
+            ]
+        );
+        assert!(matches!(report.failures[3].error, GymError::InvalidRender(_)));
+        assert!(report.to_string().starts_with("5 environment check(s) failed"));
+    }
+
+    #[test]
+    fn step_errors_are_not_action_space_failures() {
+        let report = check_env(&mut NeverReadyEnv);
+        let checks: Vec<Check> = report.failures.iter().map(|f| f.check).collect();
+        assert_eq!(checks, vec![Check::StepAfterReset]);
+        assert!(matches!(report.failures[0].error, GymError::NotReady(_)));
+    }
+}
@@ -1,7 +1,11 @@
 pub mod rng;
 pub mod render;
 pub mod render2d;
+pub mod ascii;
+pub mod env_checker;
 
 pub use rng::{RngStream, SeedSequence, rng_from_seed, sample_u64, split_n};
 pub use render::{encode_png, save_png};
-pub use render2d::{Canvas, Color, BLACK, WHITE, RED, GREEN, BLUE, GRAY};
\ No newline at end of file
+pub use render2d::{Canvas, Color, BLACK, WHITE, RED, GREEN, BLUE, GRAY};
+pub use ascii::{TextCanvas, to_cell};
+pub use env_checker::{check_env, check_env_with, Check, CheckFailure, CheckOptions, CheckReport};
@@ -101,7 +101,7 @@ impl Canvas {
     /// Draw a filled circle centered at (cx, cy) with radius r.
     pub fn fill_circle(&mut self, cx: i32, cy: i32, r: i32, color: Color) {
         if r <= 0 { return; }
-        let r2 = (r as i32) * (r as i32);
+        let r2 = r * r;
         let y_min = (cy - r).max(0);
         let y_max = (cy + r).min(self.height as i32 - 1);
         for y in y_min..=y_max {
@@ -0,0 +1,213 @@
+// Batched spaces for vector environments (Gymnasium's `vector.utils.batch_space`, `iterate` and
+// `concatenate`).
+
+use std::collections::BTreeMap;
+
+use crate::spaces::{
+    BoxScalar, BoxSpace, DictSpace, Discrete, GraphSpace, MultiBinary, MultiDiscrete, OneOf, PackedMultiBinary,
+    SequenceSpace, ShapedBoxSpace, Space, TextSpace, TupleSpace,
+};
+
+/// Spaces that can be stacked into the space of a batch of `n` elements, one per sub-env:
+/// - `Discrete` -> `MultiDiscrete` with `n` copies of the range
+/// - `MultiBinary`, `MultiDiscrete`, `BoxSpace`, `ShapedBoxSpace` -> `ShapedBoxSpace` with a
+///   leading batch dimension (`[n, ..shape]`), elements flat and row-major
+/// - tuples, `TupleSpace` and `DictSpace` -> the same structure of batched components
+/// - `PackedMultiBinary`, `TextSpace`, `SequenceSpace`, `GraphSpace`, `OneOf` -> a `SequenceSpace`
+///   of exactly `n` elements (Gymnasium's tuple of copies)
+///
+/// `sample` and `contains` of the batched space act on the whole batch at once.
+pub trait Batch: Space {
+    type Batched: Space;
+
+    /// The space of `n` stacked elements.
+    fn batch_space(&self, n: usize) -> Self::Batched;
+
+    /// Split a batch into its elements, in sub-env order.
+    ///
+    /// Panics if the batch is not a whole number of elements of this space.
+    fn iterate(&self, batch: <Self::Batched as Space>::Element) -> Vec<Self::Element>;
+
+    /// Stack elements into a batch; inverse of `iterate`.
+    ///
+    /// Panics if the elements do not have the shape of this space.
+    fn concatenate(&self, items: Vec<Self::Element>) -> <Self::Batched as Space>::Element;
+}
+
+/// See `Batch::batch_space`.
+pub fn batch_space<S: Batch>(space: &S, n: usize) -> S::Batched { space.batch_space(n) }
+
+/// See `Batch::iterate`. `space` is the space of a single element.
+pub fn iterate<S: Batch>(space: &S, batch: <S::Batched as Space>::Element) -> Vec<S::Element> { space.iterate(batch) }
+
+/// See `Batch::concatenate`. `space` is the space of a single element.
+pub fn concatenate<S: Batch>(space: &S, items: Vec<S::Element>) -> <S::Batched as Space>::Element {
+    space.concatenate(items)
+}
+
+/// Bounds repeated once per batch entry.
+fn repeat<T: Copy>(bounds: &[T], n: usize) -> Vec<T> { bounds.iter().copied().cycle().take(bounds.len() * n).collect() }
+
+/// Split a flat batch into rows of `len` values.
+fn rows<T: Clone>(flat: &[T], len: usize, space: &str) -> Vec<Vec<T>> {
+    assert!(len > 0, "{space}: cannot split a batch of empty elements");
+    assert!(flat.len().is_multiple_of(len), "{space}: batch of {} values is not a multiple of {len}", flat.len());
+    flat.chunks(len).map(<[T]>::to_vec).collect()
+}
+
+/// Concatenate rows that must all hold `len` values.
+fn stack<T>(items: Vec<Vec<T>>, len: usize, space: &str) -> Vec<T> {
+    for (i, item) in items.iter().enumerate() {
+        assert_eq!(item.len(), len, "{space}: item {i} has the wrong length");
+    }
+    items.into_iter().flatten().collect()
+}
+
+impl Batch for Discrete {
+    type Batched = MultiDiscrete;
+
+    fn batch_space(&self, n: usize) -> MultiDiscrete { MultiDiscrete::with_start(vec![self.n(); n], vec![self.start(); n]) }
+    fn iterate(&self, batch: Vec<u32>) -> Vec<u32> { batch }
+    fn concatenate(&self, items: Vec<u32>) -> Vec<u32> { items }
+}
+
+impl Batch for MultiBinary {
+    type Batched = ShapedBoxSpace<u8>;
+
+    fn batch_space(&self, n: usize) -> ShapedBoxSpace<u8> { ShapedBoxSpace::uniform([n, self.n()], 0, 1) }
+    fn iterate(&self, batch: Vec<u8>) -> Vec<Vec<u8>> { rows(&batch, self.n(), "MultiBinary") }
+    fn concatenate(&self, items: Vec<Vec<u8>>) -> Vec<u8> { stack(items, self.n(), "MultiBinary") }
+}
+
+impl Batch for MultiDiscrete {
+    type Batched = ShapedBoxSpace<u32>;
+
+    /// Bounds are `[start, start + nvec - 1]` per dimension, as in Gymnasium.
+    fn batch_space(&self, n: usize) -> ShapedBoxSpace<u32> {
+        let high: Vec<u32> = self.nvec().iter().zip(self.start()).map(|(&k, &s)| s + k - 1).collect();
+        ShapedBoxSpace::new([n, self.ndim()], repeat(self.start(), n), repeat(&high, n))
+    }
+    fn iterate(&self, batch: Vec<u32>) -> Vec<Vec<u32>> { rows(&batch, self.ndim(), "MultiDiscrete") }
+    fn concatenate(&self, items: Vec<Vec<u32>>) -> Vec<u32> { stack(items, self.ndim(), "MultiDiscrete") }
+}
+
+impl<T: BoxScalar, const N: usize> Batch for BoxSpace<T, N> {
+    type Batched = ShapedBoxSpace<T>;
+
+    fn batch_space(&self, n: usize) -> ShapedBoxSpace<T> {
+        ShapedBoxSpace::new([n, N], repeat(self.low(), n), repeat(self.high(), n))
+    }
+    fn iterate(&self, batch: Vec<T>) -> Vec<[T; N]> {
+        rows(&batch, N, "BoxSpace").into_iter().map(|row| row.try_into().expect("rows have N values")).collect()
+    }
+    fn concatenate(&self, items: Vec<[T; N]>) -> Vec<T> { items.into_iter().flatten().collect() }
+}
+
+impl<T: BoxScalar> Batch for ShapedBoxSpace<T> {
+    type Batched = ShapedBoxSpace<T>;
+
+    fn batch_space(&self, n: usize) -> ShapedBoxSpace<T> {
+        let shape: Vec<usize> = std::iter::once(n).chain(self.shape().iter().copied()).collect();
+        ShapedBoxSpace::new(shape, repeat(self.low(), n), repeat(self.high(), n))
+    }
+    fn iterate(&self, batch: Vec<T>) -> Vec<Vec<T>> { rows(&batch, self.len(), "ShapedBoxSpace") }
+    fn concatenate(&self, items: Vec<Vec<T>>) -> Vec<T> { stack(items, self.len(), "ShapedBoxSpace") }
+}
+
+impl<S: Batch> Batch for TupleSpace<S> {
+    type Batched = TupleSpace<S::Batched>;
+
+    fn batch_space(&self, n: usize) -> Self::Batched { TupleSpace::new(self.spaces().batch_space(n)) }
+    fn iterate(&self, batch: <S::Batched as Space>::Element) -> Vec<S::Element> { self.spaces().iterate(batch) }
+    fn concatenate(&self, items: Vec<S::Element>) -> <S::Batched as Space>::Element { self.spaces().concatenate(items) }
+}
+
+macro_rules! batch_tuple {
+    ($($S:ident $i:tt),+) => {
+        impl<$($S: Batch),+> Batch for ($($S,)+) {
+            type Batched = ($($S::Batched,)+);
+
+            fn batch_space(&self, n: usize) -> Self::Batched { ($(self.$i.batch_space(n),)+) }
+
+            fn iterate(&self, batch: <Self::Batched as Space>::Element) -> Vec<Self::Element> {
+                let mut columns = ($(self.$i.iterate(batch.$i).into_iter(),)+);
+                let n = columns.0.len();
+                $(assert_eq!(columns.$i.len(), n, "tuple component {} has a different batch size", $i);)+
+                (0..n).map(|_| ($(columns.$i.next().expect("checked length"),)+)).collect()
+            }
+
+            fn concatenate(&self, items: Vec<Self::Element>) -> <Self::Batched as Space>::Element {
+                let mut columns = ($(Vec::<$S::Element>::with_capacity(items.len()),)+);
+                for item in items {
+                    $(columns.$i.push(item.$i);)+
+                }
+                ($(self.$i.concatenate(columns.$i),)+)
+            }
+        }
+    };
+}
+
+batch_tuple!(A 0);
+batch_tuple!(A 0, B 1);
+batch_tuple!(A 0, B 1, C 2);
+batch_tuple!(A 0, B 1, C 2, D 3);
+batch_tuple!(A 0, B 1, C 2, D 3, E 4);
+batch_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+batch_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+batch_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<S: Batch> Batch for DictSpace<S> {
+    type Batched = DictSpace<S::Batched>;
+
+    fn batch_space(&self, n: usize) -> Self::Batched { DictSpace::new(self.iter().map(|(k, s)| (k, s.batch_space(n)))) }
+
+    /// An empty dict has no batch size, so its batches split into no elements.
+    fn iterate(&self, mut batch: BTreeMap<String, <S::Batched as Space>::Element>) -> Vec<Self::Element> {
+        let mut columns: Vec<(&str, std::vec::IntoIter<S::Element>)> = self
+            .iter()
+            .map(|(k, s)| {
+                let values = batch.remove(k).unwrap_or_else(|| panic!("DictSpace: batch is missing key {k:?}"));
+                (k, s.iterate(values).into_iter())
+            })
+            .collect();
+        let n = columns.first().map_or(0, |(_, c)| c.len());
+        for (k, c) in &columns {
+            assert_eq!(c.len(), n, "DictSpace key {k:?} has a different batch size");
+        }
+        (0..n).map(|_| columns.iter_mut().map(|(k, c)| (k.to_string(), c.next().expect("checked length"))).collect()).collect()
+    }
+
+    fn concatenate(&self, mut items: Vec<Self::Element>) -> BTreeMap<String, <S::Batched as Space>::Element> {
+        self.iter()
+            .map(|(k, s)| {
+                let column = items
+                    .iter_mut()
+                    .enumerate()
+                    .map(|(i, item)| item.remove(k).unwrap_or_else(|| panic!("DictSpace: item {i} is missing key {k:?}")))
+                    .collect();
+                (k.to_string(), s.concatenate(column))
+            })
+            .collect()
+    }
+}
+
+/// Spaces without a stacked form batch as a `SequenceSpace` of exactly `n` copies.
+macro_rules! batch_as_sequence {
+    ($(impl<$($G:ident),*> for $space:ty;)*) => {$(
+        impl<$($G: Space + Clone),*> Batch for $space {
+            type Batched = SequenceSpace<Self>;
+
+            fn batch_space(&self, n: usize) -> SequenceSpace<Self> { SequenceSpace::bounded(self.clone(), n, n) }
+            fn iterate(&self, batch: Vec<Self::Element>) -> Vec<Self::Element> { batch }
+            fn concatenate(&self, items: Vec<Self::Element>) -> Vec<Self::Element> { items }
+        }
+    )*};
+}
+
+batch_as_sequence! {
+    impl<> for PackedMultiBinary;
+    impl<> for TextSpace;
+    impl<S> for SequenceSpace<S>;
+    impl<N, E> for GraphSpace<N, E>;
+    impl<S> for OneOf<S>;
+}
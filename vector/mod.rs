@@ -1,13 +1,19 @@
 // Vectorized environments (Step 7 of README)
 // A simple synchronous vector environment running N copies of an Env in a loop.
 
-use crate::core::{Env, RenderFrame, Step};
+#[path = "batch.rs"]
+pub mod batch;
+
+use crate::core::{Env, Info, InfoValue, RenderFrame, Step};
+use crate::spaces::ActionMask;
+pub use batch::{batch_space, concatenate, iterate, Batch};
 
 /// Runs N copies of an environment in the current thread.
 ///
 /// - Construct with `SyncVectorEnv::new(n, || MyEnv::default())`
 /// - Step with a batch of actions: `step_all(actions)`
 /// - Reset all envs (optionally with a base seed): `reset_all(Some(0))`
+/// - Sample a batch of actions: `iterate(&v.single_action_space(), v.action_space().sample(rng))`
 pub struct SyncVectorEnv<E: Env> {
     envs: Vec<E>,
 }
@@ -44,11 +50,11 @@ impl<E: Env> SyncVectorEnv<E> {
 
     /// Step all environments with a batch of actions.
     /// The length of `actions` must equal `self.len()`.
-    pub fn step_all(&mut self, actions: Vec<E::Act>) -> Vec<Step<E::Obs>> {
+    pub fn step_all(&mut self, actions: Vec<E::Act>) -> Vec<Step<E::Obs, E::Reward>> {
         assert_eq!(actions.len(), self.envs.len(), "actions len must match envs len");
         self.envs
             .iter_mut()
-            .zip(actions.into_iter())
+            .zip(actions)
             .map(|(e, a)| e.step(a))
             .collect()
     }
@@ -63,23 +69,101 @@ impl<E: Env> SyncVectorEnv<E> {
         for e in &mut self.envs { e.close(); }
     }
 
+    /// Observation space of each environment (that of the first one). Panics if empty.
+    pub fn single_observation_space(&self) -> E::ObsSpace { self.envs[0].observation_space() }
+    /// Action space of each environment (that of the first one). Panics if empty.
+    pub fn single_action_space(&self) -> E::ActSpace { self.envs[0].action_space() }
+
+    /// Space of the stacked observations of all environments (see `Batch`).
+    pub fn observation_space(&self) -> <E::ObsSpace as Batch>::Batched
+    where
+        E::ObsSpace: Batch,
+    {
+        self.single_observation_space().batch_space(self.len())
+    }
+
+    /// Space of the stacked actions of all environments (see `Batch`).
+    pub fn action_space(&self) -> <E::ActSpace as Batch>::Batched
+    where
+        E::ActSpace: Batch,
+    {
+        self.single_action_space().batch_space(self.len())
+    }
+
     /// Get immutable access to underlying envs (advanced usage).
     pub fn envs(&self) -> &[E] { &self.envs }
     /// Get mutable access to underlying envs (advanced usage).
     pub fn envs_mut(&mut self) -> &mut [E] { &mut self.envs }
 }
 
+/// Per-env action masks reported in a batch of infos (see `ActionMask::from_info`), e.g.
+/// `action_masks(steps.iter().map(|s| &s.info))`; `None` for envs that reported no mask.
+pub fn action_masks<'a, I: IntoIterator<Item = &'a Info>>(infos: I) -> Vec<Option<ActionMask>> {
+    infos.into_iter().map(ActionMask::from_info).collect()
+}
+
+/// Aggregate per-environment infos into a single Info, Gymnasium-style.
+///
+/// For every key, scalar values are stacked into one array slot per env (bools become 0/1 bytes,
+/// missing slots are zero-filled) and a `"_<key>"` byte mask marks which envs reported the key.
+/// Other value types are collected in a nested map keyed by env index.
+pub fn aggregate_infos(infos: &[Info]) -> Info {
+    let mut keys: Vec<&str> = Vec::new();
+    for info in infos {
+        for (k, _) in info.iter() {
+            if !keys.contains(&k) { keys.push(k); }
+        }
+    }
+
+    let mut out = Info::new();
+    for key in keys {
+        let values: Vec<Option<&InfoValue>> = infos.iter().map(|i| i.get(key)).collect();
+        let present = || values.iter().flatten();
+        let stacked = if present().all(|v| matches!(v, InfoValue::Bool(_))) {
+            InfoValue::Bytes(values.iter().map(|v| v.and_then(InfoValue::as_bool).map_or(0, u8::from)).collect())
+        } else if present().all(|v| matches!(v, InfoValue::I64(_))) {
+            InfoValue::I64Array(values.iter().map(|v| v.and_then(InfoValue::as_i64).unwrap_or(0)).collect())
+        } else if present().all(|v| matches!(v, InfoValue::F64(_) | InfoValue::I64(_))) {
+            InfoValue::F64Array(
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Some(InfoValue::F64(x)) => *x,
+                        Some(InfoValue::I64(x)) => *x as f64,
+                        _ => 0.0,
+                    })
+                    .collect(),
+            )
+        } else {
+            let mut per_env = Info::new();
+            for (i, v) in values.iter().enumerate() {
+                if let Some(v) = v { per_env.insert(i.to_string(), (*v).clone()); }
+            }
+            InfoValue::Map(per_env)
+        };
+        out.insert(key, stacked);
+        out.insert(format!("_{key}"), InfoValue::Bytes(values.iter().map(|v| u8::from(v.is_some())).collect()));
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::{Info, RenderFrame};
+    use crate::spaces::Discrete;
 
     // A tiny dummy environment to validate vector stepping
     #[derive(Clone, Default)]
-    struct DummyEnv { s: i32 }
+    struct DummyEnv { s: u32 }
     impl Env for DummyEnv {
-        type Obs = i32;
-        type Act = i32;
+        type Obs = u32;
+        type Act = u32;
+        type ObsSpace = Discrete;
+        type ActSpace = Discrete;
+        type Reward = f32;
+        fn observation_space(&self) -> Self::ObsSpace { Discrete::new(16) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(4) }
         fn reset(&mut self, _seed: Option<u64>) -> (Self::Obs, Info) { self.s = 0; (self.s, Info::new()) }
         fn step(&mut self, a: Self::Act) -> Step<Self::Obs> {
             self.s += a;
@@ -90,7 +174,7 @@ mod tests {
 
     #[test]
     fn vector_env_runs_batch() {
-        let mut v = SyncVectorEnv::new(3, || DummyEnv::default());
+        let mut v = SyncVectorEnv::new(3, DummyEnv::default);
         let _ = v.reset_all(Some(123));
         let steps = v.step_all(vec![1, 2, 3]);
         assert_eq!(steps.len(), 3);
@@ -102,4 +186,112 @@ mod tests {
         assert!(matches!(frames[0], Some(RenderFrame::Text(_))));
         v.close_all();
     }
+
+    /// A 1-D corridor of length 4: moving left (0) is invalid at the left wall, right (1) at the right wall.
+    #[derive(Default)]
+    struct Corridor { pos: u32 }
+    impl Corridor {
+        fn info(&self) -> Info {
+            let mut info = Info::new();
+            ActionMask::new([self.pos > 0, self.pos < 3]).insert_into(&mut info);
+            info
+        }
+    }
+    impl Env for Corridor {
+        type Obs = u32;
+        type Act = u32;
+        type ObsSpace = Discrete;
+        type ActSpace = Discrete;
+        type Reward = f32;
+        fn observation_space(&self) -> Self::ObsSpace { Discrete::new(4) }
+        fn action_space(&self) -> Self::ActSpace { Discrete::new(2) }
+        fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+            self.pos = seed.map_or(0, |s| (s % 2) as u32 * 3);
+            (self.pos, self.info())
+        }
+        fn step(&mut self, a: Self::Act) -> Step<Self::Obs> {
+            self.pos = if a == 0 { self.pos - 1 } else { self.pos + 1 };
+            Step::new(self.pos, 0.0, false, false, self.info())
+        }
+    }
+
+    #[test]
+    fn action_masks_flow_through_vector_env() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut v = SyncVectorEnv::new(2, Corridor::default);
+        let resets = v.reset_all(Some(0));
+        let mut masks = action_masks(resets.iter().map(|(_, info)| info));
+        assert_eq!(masks[0], Some(ActionMask::new([false, true])));
+        assert_eq!(masks[1], Some(ActionMask::new([true, false])));
+        for _ in 0..20 {
+            let space = v.envs()[0].action_space();
+            let actions = masks.iter().map(|m| space.sample_masked(&mut rng, m.as_ref().unwrap()).unwrap()).collect();
+            let steps = v.step_all(actions);
+            assert!(steps.iter().all(|s| s.observation < 4));
+            masks = action_masks(steps.iter().map(|s| &s.info));
+        }
+        assert_eq!(action_masks([&Info::new()]), vec![None]);
+    }
+
+    #[test]
+    fn batched_spaces_sample_iterate_and_concatenate() {
+        use crate::spaces::{BoxSpace, DictSpace, MultiBinary, MultiDiscrete, ShapedBoxSpace, Space, TextSpace};
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        let mut v = SyncVectorEnv::new(3, DummyEnv::default);
+        assert_eq!(v.single_action_space(), Discrete::new(4));
+        assert_eq!(v.action_space(), MultiDiscrete::new([4, 4, 4]));
+        assert_eq!(v.observation_space(), MultiDiscrete::new([16, 16, 16]));
+        v.reset_all(Some(0));
+        let actions = iterate(&v.single_action_space(), v.action_space().sample(&mut rng));
+        assert_eq!(v.step_all(actions).len(), 3);
+
+        assert_eq!(batch_space(&Discrete::with_start(3, 1), 2), MultiDiscrete::with_start([3, 3], [1, 1]));
+        assert_eq!(batch_space(&MultiBinary::new(3), 2), ShapedBoxSpace::uniform([2, 3], 0, 1));
+        assert_eq!(
+            batch_space(&MultiDiscrete::with_start([2, 3], [0, 5]), 2),
+            ShapedBoxSpace::new([2, 2], vec![0, 5, 0, 5], vec![1, 7, 1, 7])
+        );
+        let boxed = BoxSpace::new([0.0f32, -1.0], [1.0, 1.0]);
+        let batched = batch_space(&boxed, 3);
+        assert_eq!(batched.shape(), &[3, 2]);
+        assert!(!batched.contains(&vec![0.5, 0.0, 0.5, 0.0, 0.5, -2.0]));
+        let batch = batched.sample(&mut rng);
+        assert!(batched.contains(&batch));
+        let items = iterate(&boxed, batch.clone());
+        assert!(items.iter().all(|x| boxed.contains(x)));
+        assert_eq!(concatenate(&boxed, items), batch);
+
+        let space = (Discrete::new(2), DictSpace::new([("pos", ShapedBoxSpace::uniform([2, 2], 0u8, 9))]), TextSpace::new(1, 3));
+        let batched = batch_space(&space, 4);
+        assert_eq!(batched.1.get("pos").unwrap().shape(), &[4, 2, 2]);
+        assert_eq!((batched.2.min_length(), batched.2.max_length()), (4, Some(4)));
+        let batch = batched.sample(&mut rng);
+        assert!(batched.contains(&batch));
+        let items = iterate(&space, batch.clone());
+        assert_eq!(items.len(), 4);
+        assert!(items.iter().all(|x| space.contains(x)));
+        assert_eq!(concatenate(&space, items), batch);
+    }
+
+    #[test]
+    fn aggregate_infos_stacks_scalars_with_masks() {
+        let mut a = Info::new();
+        a.insert("episode_return", InfoValue::from(3.5));
+        a.insert("done", InfoValue::from(true));
+        a.insert("name", InfoValue::from("a"));
+        let mut b = Info::new();
+        b.insert("done", InfoValue::from(false));
+        b.insert("lives", InfoValue::from(2i64));
+
+        let agg = aggregate_infos(&[a, b]);
+        assert_eq!(agg.get_f64_array("episode_return"), Some(&[3.5, 0.0][..]));
+        assert_eq!(agg.get_bytes("_episode_return"), Some(&[1, 0][..]));
+        assert_eq!(agg.get_bytes("done"), Some(&[1, 0][..]));
+        assert_eq!(agg.get_i64_array("lives"), Some(&[0, 2][..]));
+        assert_eq!(agg.get_map("name").and_then(|m| m.get_str("0")), Some("a"));
+        assert_eq!(agg.get_bytes("_name"), Some(&[1, 0][..]));
+    }
 }
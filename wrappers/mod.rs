@@ -5,9 +5,14 @@
 // - ClipAction
 // - ClipReward
 // - TransformObservation / TransformAction / TransformReward
+// - LinearReward (multi-objective scalarization)
 // - RecordEpisodeStatistics
+// - OrderEnforcing / PassiveEnvChecker (applied by registry::make according to the EnvSpec)
 
-use crate::core::{Env, Info, InfoValue, Step};
+use crate::core::{
+    check_action, Env, EnvMetadata, GymError, Info, InfoValue, RenderMode, ResetOptions, Result, Reward, StatefulEnv, Step,
+};
+use crate::spaces::{BoxScalar, BoxSpace, Space};
 
 /// A wrapper that enforces a maximum number of steps per episode, marking truncation when exceeded.
 pub struct TimeLimit<E: Env> {
@@ -24,181 +29,346 @@ impl<E: Env> TimeLimit<E> {
     pub fn inner(&self) -> &E { &self.inner }
     pub fn inner_mut(&mut self) -> &mut E { &mut self.inner }
     pub fn into_inner(self) -> E { self.inner }
+
+    fn count_step(&mut self, mut s: Step<E::Obs, E::Reward>) -> Step<E::Obs, E::Reward> {
+        self.steps += 1;
+        if !s.terminated && !s.truncated && self.steps >= self.max_steps {
+            s.truncated = true;
+        }
+        s
+    }
 }
 
 impl<E: Env> Env for TimeLimit<E> {
     type Obs = E::Obs;
     type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
         self.steps = 0;
         self.inner.reset(seed)
     }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
-        let mut s = self.inner.step(action);
-        self.steps += 1;
-        if !s.terminated && !s.truncated && self.steps >= self.max_steps {
-            s.truncated = true;
-        }
-        s
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.steps = 0;
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let s = self.inner.step(action);
+        self.count_step(s)
+    }
+
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset(seed)?;
+        self.steps = 0;
+        Ok(out)
     }
 
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset_with_options(seed, options)?;
+        self.steps = 0;
+        Ok(out)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let s = self.inner.try_step(action)?;
+        Ok(self.count_step(s))
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
-/// ClipAction clamps scalar actions to a range [min, max]. Useful for continuous controls.
-/// For non-scalar actions, prefer TransformAction to perform custom clipping.
-pub struct ClipAction<E: Env, A: PartialOrd + Copy> {
+/// ClipAction clamps each dimension of a Box action into [low, high] before forwarding it.
+/// Like Gymnasium's wrapper it accepts any action, so its action space is the unbounded Box
+/// (±infinity for floats, the integer limits otherwise).
+pub struct ClipAction<E, T, const N: usize>
+where
+    E: Env<Act = [T; N]>,
+    T: Copy + PartialOrd,
+{
     inner: E,
-    min: A,
-    max: A,
-    _marker: core::marker::PhantomData<A>,
+    low: [T; N],
+    high: [T; N],
 }
 
-impl<E, A> ClipAction<E, A>
+impl<E, T, const N: usize> ClipAction<E, T, N>
 where
-    E: Env<Act = A>,
-    A: PartialOrd + Copy,
+    E: Env<Act = [T; N]>,
+    T: Copy + PartialOrd,
 {
-    pub fn new(inner: E, min: A, max: A) -> Self {
-        Self { inner, min, max, _marker: core::marker::PhantomData }
+    pub fn new(inner: E, low: [T; N], high: [T; N]) -> Self {
+        for i in 0..N {
+            assert!(low[i] <= high[i], "low[{i}] > high[{i}]");
+        }
+        Self { inner, low, high }
+    }
+
+    fn clip(&self, mut action: [T; N]) -> [T; N] {
+        for (i, v) in action.iter_mut().enumerate() {
+            let (lo, hi) = (self.low[i], self.high[i]);
+            if *v < lo { *v = lo; } else if *v > hi { *v = hi; }
+        }
+        action
     }
 }
 
-impl<E, A> Env for ClipAction<E, A>
+impl<E, T, const N: usize> Env for ClipAction<E, T, N>
 where
-    E: Env<Act = A>,
-    A: PartialOrd + Copy,
+    E: Env<Act = [T; N]>,
+    T: BoxScalar,
 {
     type Obs = E::Obs;
-    type Act = A;
+    type Act = [T; N];
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = BoxSpace<T, N>;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { BoxSpace::new([T::LOWEST; N], [T::HIGHEST; N]) }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
-        let a = if action < self.min { self.min } else if action > self.max { self.max } else { action };
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let a = self.clip(action);
         self.inner.step(a)
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { self.inner.try_reset(seed) }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let a = self.clip(action);
+        self.inner.try_step(a)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
-/// ClipReward clamps rewards into [min, max].
+/// ClipReward clamps rewards into [min, max]; vector rewards are clamped component-wise.
 pub struct ClipReward<E: Env> {
     inner: E,
-    min: f32,
-    max: f32,
+    min: <E::Reward as Reward>::Scalar,
+    max: <E::Reward as Reward>::Scalar,
 }
 
 impl<E: Env> ClipReward<E> {
-    pub fn new(inner: E, min: f32, max: f32) -> Self { Self { inner, min, max } }
+    pub fn new(inner: E, min: <E::Reward as Reward>::Scalar, max: <E::Reward as Reward>::Scalar) -> Self {
+        Self { inner, min, max }
+    }
+
+    fn clip(&self, mut s: Step<E::Obs, E::Reward>) -> Step<E::Obs, E::Reward> {
+        let (min, max) = (self.min, self.max);
+        s.reward = s.reward.map_components(|r| if r < min { min } else if r > max { max } else { r });
+        s
+    }
 }
 
 impl<E: Env> Env for ClipReward<E> {
     type Obs = E::Obs;
     type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
-        let mut s = self.inner.step(action);
-        if s.reward < self.min { s.reward = self.min; }
-        if s.reward > self.max { s.reward = self.max; }
-        s
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let s = self.inner.step(action);
+        self.clip(s)
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { self.inner.try_reset(seed) }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let s = self.inner.try_step(action)?;
+        Ok(self.clip(s))
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
 /// TransformObservation maps an environment's observations through a user-provided function.
-pub struct TransformObservation<E, F, O2>
+/// The caller supplies the observation space of the transformed observations.
+pub struct TransformObservation<E, F, S>
 where
     E: Env,
-    F: Fn(&E::Obs) -> O2,
+    S: Space,
+    F: Fn(&E::Obs) -> S::Element,
 {
     inner: E,
     f: F,
-    _marker: core::marker::PhantomData<O2>,
+    space: S,
 }
 
-impl<E, F, O2> TransformObservation<E, F, O2>
+impl<E, F, S> TransformObservation<E, F, S>
 where
     E: Env,
-    F: Fn(&E::Obs) -> O2,
+    S: Space,
+    F: Fn(&E::Obs) -> S::Element,
 {
-    pub fn new(inner: E, f: F) -> Self { Self { inner, f, _marker: core::marker::PhantomData } }
+    pub fn new(inner: E, f: F, observation_space: S) -> Self { Self { inner, f, space: observation_space } }
 }
 
-impl<E, F, O2> Env for TransformObservation<E, F, O2>
+impl<E, F, S> Env for TransformObservation<E, F, S>
 where
     E: Env,
-    F: Fn(&E::Obs) -> O2,
+    S: Space + Clone,
+    F: Fn(&E::Obs) -> S::Element,
 {
-    type Obs = O2;
+    type Obs = S::Element;
     type Act = E::Act;
+    type ObsSpace = S;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.space.clone() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
         let (obs, info) = self.inner.reset(seed);
         ((self.f)(&obs), info)
     }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        let (obs, info) = self.inner.reset_with_options(seed, options);
+        ((self.f)(&obs), info)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
         let s = self.inner.step(action);
         Step::new((self.f)(&s.observation), s.reward, s.terminated, s.truncated, s.info)
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> {
+        let (obs, info) = self.inner.try_reset(seed)?;
+        Ok(((self.f)(&obs), info))
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let (obs, info) = self.inner.try_reset_with_options(seed, options)?;
+        Ok(((self.f)(&obs), info))
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let s = self.inner.try_step(action)?;
+        Ok(Step::new((self.f)(&s.observation), s.reward, s.terminated, s.truncated, s.info))
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
 /// TransformAction maps caller-provided actions into the inner environment's action type.
-pub struct TransformAction<E, F, A2>
+/// The caller supplies the action space of the actions accepted by the wrapper.
+pub struct TransformAction<E, F, S>
 where
     E: Env,
-    F: Fn(A2) -> E::Act,
+    S: Space,
+    F: Fn(S::Element) -> E::Act,
 {
     inner: E,
     f: F,
-    _marker: core::marker::PhantomData<A2>,
+    space: S,
 }
 
-impl<E, F, A2> TransformAction<E, F, A2>
+impl<E, F, S> TransformAction<E, F, S>
 where
     E: Env,
-    F: Fn(A2) -> E::Act,
+    S: Space,
+    F: Fn(S::Element) -> E::Act,
 {
-    pub fn new(inner: E, f: F) -> Self { Self { inner, f, _marker: core::marker::PhantomData } }
+    pub fn new(inner: E, f: F, action_space: S) -> Self { Self { inner, f, space: action_space } }
 }
 
-impl<E, F, A2> Env for TransformAction<E, F, A2>
+impl<E, F, S> Env for TransformAction<E, F, S>
 where
     E: Env,
-    F: Fn(A2) -> E::Act,
-    A2: Clone,
+    S: Space + Clone,
+    F: Fn(S::Element) -> E::Act,
 {
     type Obs = E::Obs;
-    type Act = A2;
+    type Act = S::Element;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = S;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.space.clone() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
         let inner_action = (self.f)(action);
         self.inner.step(inner_action)
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { self.inner.try_reset(seed) }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        check_action(&self.space, &action)?;
+        self.inner.try_step((self.f)(action))
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
 /// TransformReward maps rewards through a user-provided function (e.g., scaling).
+/// Vector rewards are transformed component-wise.
 pub struct TransformReward<E, F>
 where
     E: Env,
-    F: Fn(f32) -> f32,
+    F: Fn(<E::Reward as Reward>::Scalar) -> <E::Reward as Reward>::Scalar,
 {
     inner: E,
     f: F,
@@ -207,7 +377,7 @@ where
 impl<E, F> TransformReward<E, F>
 where
     E: Env,
-    F: Fn(f32) -> f32,
+    F: Fn(<E::Reward as Reward>::Scalar) -> <E::Reward as Reward>::Scalar,
 {
     pub fn new(inner: E, f: F) -> Self { Self { inner, f } }
 }
@@ -215,71 +385,545 @@ where
 impl<E, F> Env for TransformReward<E, F>
 where
     E: Env,
-    F: Fn(f32) -> f32,
+    F: Fn(<E::Reward as Reward>::Scalar) -> <E::Reward as Reward>::Scalar,
 {
     type Obs = E::Obs;
     type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
         let mut s = self.inner.step(action);
-        s.reward = (self.f)(s.reward);
+        s.reward = s.reward.map_components(&self.f);
         s
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { self.inner.try_reset(seed) }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let mut s = self.inner.try_step(action)?;
+        s.reward = s.reward.map_components(&self.f);
+        Ok(s)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
+    fn close(&mut self) { self.inner.close() }
+}
+
+/// LinearReward scalarizes a vector reward as the dot product with fixed weights, as in
+/// MO-Gymnasium. The original components are kept in Info under "vector_reward".
+/// Weights have the type of the reward, so their number is checked at compile time for
+/// fixed-size rewards; with `Vec` rewards a length mismatch makes `try_step` return
+/// `GymError::InvalidArgument` and `step` panic.
+pub struct LinearReward<E: Env> {
+    inner: E,
+    weights: E::Reward,
+}
+
+impl<E: Env> LinearReward<E> {
+    pub fn new(inner: E, weights: E::Reward) -> Self { Self { inner, weights } }
+
+    fn scalarize(&self, s: Step<E::Obs, E::Reward>) -> Result<Step<E::Obs, <E::Reward as Reward>::Scalar>> {
+        let (components, weights) = (s.reward.components(), self.weights.components());
+        if components.len() != weights.len() {
+            return Err(GymError::InvalidArgument(format!(
+                "LinearReward: {} weights for a reward of {} components",
+                weights.len(),
+                components.len()
+            )));
+        }
+        let reward = components.iter().zip(weights).fold(Default::default(), |acc, (&r, &w)| acc + r * w);
+        let mut info = s.info;
+        info.insert("vector_reward", InfoValue::F64Array(components.iter().map(|&r| r.into()).collect()));
+        Ok(Step::new(s.observation, reward, s.terminated, s.truncated, info))
+    }
+}
+
+impl<E: Env> Env for LinearReward<E> {
+    type Obs = E::Obs;
+    type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = <E::Reward as Reward>::Scalar;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
+
+    fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) { self.inner.reset(seed) }
+
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let s = self.inner.step(action);
+        self.scalarize(s).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> { self.inner.try_reset(seed) }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        self.inner.try_reset_with_options(seed, options)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let s = self.inner.try_step(action)?;
+        self.scalarize(s)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
+    fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
 /// RecordEpisodeStatistics tracks cumulative return and episode length.
 /// On episode end (terminated or truncated), it injects keys into the returned Step's Info:
-/// - "episode_return": f64
+/// - "episode_return": f64 (F64Array with one entry per objective for vector rewards)
 /// - "episode_length": i64
+///
+/// Returns are accumulated in f64 regardless of the reward's scalar type.
 pub struct RecordEpisodeStatistics<E: Env> {
     inner: E,
-    ep_return: f64,
+    ep_return: Vec<f64>,
     ep_length: i64,
 }
 
 impl<E: Env> RecordEpisodeStatistics<E> {
-    pub fn new(inner: E) -> Self { Self { inner, ep_return: 0.0, ep_length: 0 } }
+    pub fn new(inner: E) -> Self { Self { inner, ep_return: Vec::new(), ep_length: 0 } }
+
+    fn record(&mut self, mut s: Step<E::Obs, E::Reward>) -> Step<E::Obs, E::Reward> {
+        let components = s.reward.components();
+        self.ep_return.resize(components.len(), 0.0);
+        for (acc, &r) in self.ep_return.iter_mut().zip(components) {
+            *acc += r.into();
+        }
+        self.ep_length += 1;
+        if s.terminated || s.truncated {
+            let mut info = s.info;
+            info.insert("episode_return", E::Reward::info_value(&self.ep_return));
+            info.insert("episode_length", InfoValue::from(self.ep_length));
+            s.info = info;
+            // reset counters for next episode
+            self.ep_return.clear();
+            self.ep_length = 0;
+        }
+        s
+    }
 }
 
 impl<E: Env> Env for RecordEpisodeStatistics<E> {
     type Obs = E::Obs;
     type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
 
     fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
-        self.ep_return = 0.0;
+        self.ep_return.clear();
         self.ep_length = 0;
         self.inner.reset(seed)
     }
 
-    fn step(&mut self, action: Self::Act) -> Step<Self::Obs> {
-        let mut s = self.inner.step(action);
-        self.ep_return += s.reward as f64;
-        self.ep_length += 1;
-        if s.terminated || s.truncated {
-            let mut info = s.info;
-            info.insert("episode_return", InfoValue::from(self.ep_return));
-            info.insert("episode_length", InfoValue::from(self.ep_length));
-            s.info = info;
-            // reset counters for next episode
-            self.ep_return = 0.0;
-            self.ep_length = 0;
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.ep_return.clear();
+        self.ep_length = 0;
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        let s = self.inner.step(action);
+        self.record(s)
+    }
+
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset(seed)?;
+        self.ep_return.clear();
+        self.ep_length = 0;
+        Ok(out)
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset_with_options(seed, options)?;
+        self.ep_return.clear();
+        self.ep_length = 0;
+        Ok(out)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        let s = self.inner.try_step(action)?;
+        Ok(self.record(s))
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
+    fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
+    fn close(&mut self) { self.inner.close() }
+}
+
+/// OrderEnforcing rejects `step` and `render` before the first `reset`, like Gymnasium's wrapper.
+/// By default `try_step` and `try_render` return `GymError::NotReady`; with `with_panic(true)` they
+/// panic instead. `step` always panics since it cannot report an error, and `render` returns
+/// `None` (or panics with `with_panic(true)`), as it has no error channel either.
+pub struct OrderEnforcing<E: Env> {
+    inner: E,
+    has_reset: bool,
+    panic: bool,
+}
+
+impl<E: Env> OrderEnforcing<E> {
+    pub fn new(inner: E) -> Self { Self { inner, has_reset: false, panic: false } }
+
+    /// Panic on out-of-order calls instead of returning an error.
+    pub fn with_panic(mut self, panic: bool) -> Self {
+        self.panic = panic;
+        self
+    }
+
+    /// Whether `reset` has been called at least once.
+    pub fn has_reset(&self) -> bool { self.has_reset }
+
+    pub fn inner(&self) -> &E { &self.inner }
+    pub fn inner_mut(&mut self) -> &mut E { &mut self.inner }
+    pub fn into_inner(self) -> E { self.inner }
+
+    /// The error for calling `call` before `reset`; panics when configured to.
+    fn not_ready(&self, call: &str) -> GymError {
+        let e = GymError::NotReady(format!("OrderEnforcing: cannot call {call} before reset"));
+        if self.panic { panic!("{e}"); }
+        e
+    }
+}
+
+impl<E: Env> Env for OrderEnforcing<E> {
+    type Obs = E::Obs;
+    type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
+
+    fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        self.has_reset = true;
+        self.inner.reset(seed)
+    }
+
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        self.has_reset = true;
+        self.inner.reset_with_options(seed, options)
+    }
+
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        if !self.has_reset {
+            panic!("{}", self.not_ready("step"));
+        }
+        self.inner.step(action)
+    }
+
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset(seed)?;
+        self.has_reset = true;
+        Ok(out)
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset_with_options(seed, options)?;
+        self.has_reset = true;
+        Ok(out)
+    }
+
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        if !self.has_reset {
+            return Err(self.not_ready("step"));
+        }
+        self.inner.try_step(action)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
+
+    /// `None` before `reset` (or a panic with `with_panic(true)`); see `try_render` for the error.
+    fn render(&self) -> Option<crate::core::RenderFrame> {
+        if !self.has_reset {
+            self.not_ready("render");
+            return None;
+        }
+        self.inner.render()
+    }
+
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> {
+        if !self.has_reset {
+            return Err(self.not_ready("render"));
         }
+        self.inner.try_render()
+    }
+
+    fn close(&mut self) { self.inner.close() }
+}
+
+/// PassiveEnvChecker validates the first `reset` and the first `step` against the declared
+/// spaces (observation in `observation_space()`, action in `action_space()`), like Gymnasium's
+/// wrapper, then passes calls through unchecked.
+/// Violations make `try_reset`/`try_step` return the error. `reset`/`step` only record them in
+/// `warnings()` and carry on, as Gymnasium's checker only warns; with `with_panic(true)` they
+/// panic instead.
+pub struct PassiveEnvChecker<E: Env> {
+    inner: E,
+    checked_reset: bool,
+    checked_step: bool,
+    panic: bool,
+    warnings: Vec<GymError>,
+}
+
+impl<E: Env> PassiveEnvChecker<E> {
+    pub fn new(inner: E) -> Self {
+        Self { inner, checked_reset: false, checked_step: false, panic: false, warnings: Vec::new() }
+    }
+
+    /// Panic on violations found by `reset`/`step` instead of recording them.
+    pub fn with_panic(mut self, panic: bool) -> Self {
+        self.panic = panic;
+        self
+    }
+
+    /// Violations found by `reset`/`step`, in the order they were found.
+    pub fn warnings(&self) -> &[GymError] { &self.warnings }
+
+    pub fn inner(&self) -> &E { &self.inner }
+    pub fn inner_mut(&mut self) -> &mut E { &mut self.inner }
+    pub fn into_inner(self) -> E { self.inner }
+
+    fn check_obs(&self, obs: &E::Obs, call: &str) -> Result<()> {
+        self.inner.observation_space().validate(obs).map_err(|e| {
+            GymError::InvalidObservation(format!(
+                "PassiveEnvChecker: the observation returned by {call} is outside the observation space: {e}"
+            ))
+        })
+    }
+
+    fn check_action(&self, action: &E::Act) -> Result<()> {
+        self.inner.action_space().validate(action).map_err(|e| {
+            GymError::InvalidAction(format!("PassiveEnvChecker: action is outside the action space: {e}"))
+        })
+    }
+
+    fn check_reset(&mut self, obs: &E::Obs) -> Result<()> {
+        if self.checked_reset {
+            return Ok(());
+        }
+        self.checked_reset = true;
+        self.check_obs(obs, "reset")
+    }
+
+    /// Record a violation found on the unchecked API; panics when configured to.
+    fn warn(&mut self, result: Result<()>) {
+        if let Err(e) = result {
+            if self.panic { panic!("{e}"); }
+            self.warnings.push(e);
+        }
+    }
+}
+
+impl<E: Env> Env for PassiveEnvChecker<E> {
+    type Obs = E::Obs;
+    type Act = E::Act;
+    type ObsSpace = E::ObsSpace;
+    type ActSpace = E::ActSpace;
+    type Reward = E::Reward;
+
+    fn observation_space(&self) -> Self::ObsSpace { self.inner.observation_space() }
+    fn action_space(&self) -> Self::ActSpace { self.inner.action_space() }
+
+    fn reset(&mut self, seed: Option<u64>) -> (Self::Obs, Info) {
+        let out = self.inner.reset(seed);
+        let result = self.check_reset(&out.0);
+        self.warn(result);
+        out
+    }
+
+    fn reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> (Self::Obs, Info) {
+        let out = self.inner.reset_with_options(seed, options);
+        let result = self.check_reset(&out.0);
+        self.warn(result);
+        out
+    }
+
+    /// The first action is checked but still passed to the inner env.
+    fn step(&mut self, action: Self::Act) -> Step<Self::Obs, Self::Reward> {
+        if self.checked_step {
+            return self.inner.step(action);
+        }
+        self.checked_step = true;
+        let result = self.check_action(&action);
+        self.warn(result);
+        let s = self.inner.step(action);
+        let result = self.check_obs(&s.observation, "step");
+        self.warn(result);
         s
     }
 
+    fn try_reset(&mut self, seed: Option<u64>) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset(seed)?;
+        self.check_reset(&out.0)?;
+        Ok(out)
+    }
+
+    fn try_reset_with_options(&mut self, seed: Option<u64>, options: &ResetOptions) -> Result<(Self::Obs, Info)> {
+        let out = self.inner.try_reset_with_options(seed, options)?;
+        self.check_reset(&out.0)?;
+        Ok(out)
+    }
+
+    /// A rejected first action does not count as the checked step.
+    fn try_step(&mut self, action: Self::Act) -> Result<Step<Self::Obs, Self::Reward>> {
+        if self.checked_step {
+            return self.inner.try_step(action);
+        }
+        self.check_action(&action)?;
+        let s = self.inner.try_step(action)?;
+        self.checked_step = true;
+        self.check_obs(&s.observation, "step")?;
+        Ok(s)
+    }
+
+    fn metadata(&self) -> EnvMetadata { self.inner.metadata() }
+    fn render_mode(&self) -> RenderMode { self.inner.render_mode() }
     fn render(&self) -> Option<crate::core::RenderFrame> { self.inner.render() }
+    fn try_render(&self) -> Result<Option<crate::core::RenderFrame>> { self.inner.try_render() }
     fn close(&mut self) { self.inner.close() }
 }
 
+// State snapshots (StatefulEnv) propagate through wrappers. Wrappers with their own
+// episode bookkeeping append it to the inner state; stateless wrappers forward as-is.
+
+/// State: (inner state, elapsed steps).
+impl<E: StatefulEnv> StatefulEnv for TimeLimit<E> {
+    type State = (E::State, u32);
+
+    fn get_state(&self) -> Self::State { (self.inner.get_state(), self.steps) }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.inner.set_state(&state.0);
+        self.steps = state.1;
+    }
+}
+
+/// State: (inner state, running return per reward component, running length).
+impl<E: StatefulEnv> StatefulEnv for RecordEpisodeStatistics<E> {
+    type State = (E::State, Vec<f64>, i64);
+
+    fn get_state(&self) -> Self::State { (self.inner.get_state(), self.ep_return.clone(), self.ep_length) }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.inner.set_state(&state.0);
+        self.ep_return = state.1.clone();
+        self.ep_length = state.2;
+    }
+}
+
+/// State: (inner state, whether reset has been called).
+impl<E: StatefulEnv> StatefulEnv for OrderEnforcing<E> {
+    type State = (E::State, bool);
+
+    fn get_state(&self) -> Self::State { (self.inner.get_state(), self.has_reset) }
+
+    fn set_state(&mut self, state: &Self::State) {
+        self.inner.set_state(&state.0);
+        self.has_reset = state.1;
+    }
+}
+
+impl<E: StatefulEnv> StatefulEnv for PassiveEnvChecker<E> {
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E, T, const N: usize> StatefulEnv for ClipAction<E, T, N>
+where
+    E: StatefulEnv<Act = [T; N]>,
+    T: BoxScalar,
+{
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E: StatefulEnv> StatefulEnv for ClipReward<E> {
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E: StatefulEnv> StatefulEnv for LinearReward<E> {
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E, F, S> StatefulEnv for TransformObservation<E, F, S>
+where
+    E: StatefulEnv,
+    S: Space + Clone,
+    F: Fn(&E::Obs) -> S::Element,
+{
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E, F, S> StatefulEnv for TransformAction<E, F, S>
+where
+    E: StatefulEnv,
+    S: Space + Clone,
+    F: Fn(S::Element) -> E::Act,
+{
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
+impl<E, F> StatefulEnv for TransformReward<E, F>
+where
+    E: StatefulEnv,
+    F: Fn(<E::Reward as Reward>::Scalar) -> <E::Reward as Reward>::Scalar,
+{
+    type State = E::State;
+    fn get_state(&self) -> Self::State { self.inner.get_state() }
+    fn set_state(&mut self, state: &Self::State) { self.inner.set_state(state) }
+}
+
 // Re-exports for convenience
 pub use {
     ClipAction as _ClipAction,
     ClipReward as _ClipReward,
+    LinearReward as _LinearReward,
+    OrderEnforcing as _OrderEnforcing,
+    PassiveEnvChecker as _PassiveEnvChecker,
     RecordEpisodeStatistics as _RecordEpisodeStatistics,
     TimeLimit as _TimeLimit,
     TransformAction as _TransformAction,